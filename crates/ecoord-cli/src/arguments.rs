@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
@@ -14,5 +15,9 @@ pub enum Commands {
         /// Path to the ecoord document
         #[clap(short, long)]
         ecoord_file_path: String,
+
+        /// Print the resolved transforms at this time (e.g. "2020-04-12T22:10:57Z")
+        #[clap(long)]
+        time: Option<DateTime<Utc>>,
     },
 }
@@ -1,14 +1,23 @@
 use std::path::Path;
 
+use chrono::{DateTime, Utc};
+use ecoord::ReferenceFrames;
+use itertools::Itertools;
 use tracing::info;
 
-pub fn run(ecoord_file_path: impl AsRef<Path>) {
+pub fn run(
+    ecoord_file_path: impl AsRef<Path>,
+    time: Option<DateTime<Utc>>,
+) -> Result<(), ecoord::io::Error> {
     info!("Ecoord path: {}", &ecoord_file_path.as_ref().display());
 
-    let reference_frames = ecoord::io::EcoordReader::from_path(ecoord_file_path)
-        .unwrap()
-        .finish()
-        .unwrap();
+    let reference_frames = ecoord::io::EcoordReader::from_path(ecoord_file_path)?.finish()?;
+
+    info!(
+        "Number of transforms: {} (samples: {})",
+        reference_frames.transforms().len(),
+        reference_frames.total_sample_count()
+    );
 
     for ((current_channel_id, current_transform_id), current_transforms) in
         reference_frames.transforms()
@@ -48,4 +57,92 @@ pub fn run(ecoord_file_path: impl AsRef<Path>) {
         info!("");
         //reference_frames.transforms()
     }
+
+    if let Some(time) = time {
+        let lines = snapshot_lines(&reference_frames, &time)?;
+        info!("Transforms at {}:", time);
+        for current_line in lines {
+            info!("\t{}", current_line);
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves all transforms at the given time and formats one line per channel and transform.
+fn snapshot_lines(
+    reference_frames: &ReferenceFrames,
+    timestamp: &DateTime<Utc>,
+) -> Result<Vec<String>, ecoord::Error> {
+    let snapshot = reference_frames.get_timed_subset(timestamp)?;
+
+    let lines = snapshot
+        .transforms()
+        .iter()
+        .sorted_by_key(|(k, _)| *k)
+        .map(
+            |((current_channel_id, current_transform_id), current_transforms)| {
+                let current_transform = current_transforms.first().unwrap();
+                let (roll, pitch, yaw) = current_transform.rotation.euler_angles();
+                format!(
+                    "channel id: {} {}: translation=[{}, {}, {}] rpy=[{}, {}, {}]",
+                    current_channel_id,
+                    current_transform_id,
+                    current_transform.translation.x,
+                    current_transform.translation.y,
+                    current_transform.translation.z,
+                    roll,
+                    pitch,
+                    yaw
+                )
+            },
+        )
+        .collect();
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use ecoord::{ChannelId, FrameId, InterpolationMethod, Transform, TransformId, TransformInfo};
+    use nalgebra::{UnitQuaternion, Vector3};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_snapshot_lines() {
+        let transform_id = TransformId::new(FrameId::from("map"), FrameId::from("base_link"));
+        let mut transforms: HashMap<(ChannelId, TransformId), Vec<Transform>> = HashMap::new();
+        transforms.insert(
+            (ChannelId::from("slam"), transform_id.clone()),
+            vec![
+                Transform::new(
+                    Utc.timestamp_opt(0, 0).unwrap(),
+                    Vector3::new(0.0, 0.0, 0.0),
+                    UnitQuaternion::identity(),
+                ),
+                Transform::new(
+                    Utc.timestamp_opt(4, 0).unwrap(),
+                    Vector3::new(8.0, 4.0, 0.0),
+                    UnitQuaternion::from_euler_angles(0.0, 0.0, 1.0),
+                ),
+            ],
+        );
+        let mut transform_info: HashMap<TransformId, TransformInfo> = HashMap::new();
+        transform_info.insert(
+            transform_id,
+            TransformInfo::new(Some(InterpolationMethod::Linear), None),
+        );
+        let reference_frames =
+            ReferenceFrames::new(transforms, HashMap::new(), HashMap::new(), transform_info)
+                .unwrap();
+
+        let lines = snapshot_lines(&reference_frames, &Utc.timestamp_opt(2, 0).unwrap()).unwrap();
+
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with(
+            "channel id: slam frame_id=map child_frame_id=base_link: translation=[4, 2, 0]"
+        ));
+        assert!(lines[0].ends_with(", 0.5]"));
+    }
 }
@@ -4,16 +4,24 @@ mod commands;
 use crate::arguments::{Arguments, Commands};
 use clap::Parser;
 use std::path::PathBuf;
+use tracing::error;
 
 fn main() {
     tracing_subscriber::fmt::init();
     let arguments = Arguments::parse();
 
-    match &arguments.command {
-        Commands::Stats { ecoord_file_path } => {
+    let result = match &arguments.command {
+        Commands::Stats {
+            ecoord_file_path,
+            time,
+        } => {
             let ecoord_file_path = PathBuf::from(ecoord_file_path);
 
-            commands::stats::run(ecoord_file_path);
+            commands::stats::run(ecoord_file_path, *time)
         }
     };
+    if let Err(e) = result {
+        error!("{}", e);
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,31 @@
+use std::process::{Command, Output};
+
+fn run_stats(file_name: &str, time: &str) -> Output {
+    let ecoord_file_path = format!("{}/tests/data/{file_name}", env!("CARGO_MANIFEST_DIR"));
+    Command::new(env!("CARGO_BIN_EXE_ecoord-cli"))
+        .args(["stats", "--ecoord-file-path", &ecoord_file_path])
+        .args(["--time", time])
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_stats_with_time() {
+    let output = run_stats("dynamic.ecoord.json", "1970-01-01T00:00:02Z");
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains("Transforms at 1970-01-01 00:00:02 UTC:"));
+    assert!(stdout
+        .contains("channel id: slam frame_id=map child_frame_id=base_link: translation=[4, 2, 0]"));
+}
+
+#[test]
+fn test_stats_with_unsorted_transforms() {
+    let output = run_stats("unsorted.ecoord.json", "1970-01-01T00:00:02Z");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!stderr.contains("panicked"));
+}
@@ -0,0 +1,90 @@
+use crate::Transform;
+use chrono::{DateTime, Utc};
+use nalgebra::{Affine3, Isometry3, Matrix4, Point3, Translation3, UnitQuaternion, Vector3};
+
+/// A time-dependent affine transformation in 3D with a per-axis scale.
+///
+/// The scale is applied first, followed by the rotation and the translation. Intended for
+/// frames differing by a known anisotropic scale, such as an exaggerated vertical axis for
+/// visualization. For rigid transformations use [`Transform`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AffineTransform {
+    pub timestamp: DateTime<Utc>,
+    pub translation: Vector3<f64>,
+    pub rotation: UnitQuaternion<f64>,
+    pub scale: Vector3<f64>,
+}
+
+impl AffineTransform {
+    pub fn new(
+        timestamp: DateTime<Utc>,
+        translation: Vector3<f64>,
+        rotation: UnitQuaternion<f64>,
+        scale: Vector3<f64>,
+    ) -> Self {
+        Self {
+            timestamp,
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    pub fn affine(&self) -> Affine3<f64> {
+        let isometry = Isometry3::from_parts(Translation3::from(self.translation), self.rotation);
+        Affine3::from_matrix_unchecked(
+            isometry.to_homogeneous() * Matrix4::new_nonuniform_scaling(&self.scale),
+        )
+    }
+
+    pub fn transform_point(&self, pt: &Point3<f64>) -> Point3<f64> {
+        self.affine().transform_point(pt)
+    }
+}
+
+impl From<Transform> for AffineTransform {
+    fn from(item: Transform) -> Self {
+        Self::new(
+            item.timestamp,
+            item.translation,
+            item.rotation,
+            Vector3::new(1.0, 1.0, 1.0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_scaled_point_transformation() {
+        let affine_transform = AffineTransform::new(
+            Utc.timestamp_opt(0, 0).unwrap(),
+            Vector3::new(10.0, 0.0, 0.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, std::f64::consts::FRAC_PI_2),
+            Vector3::new(1.0, 2.0, 5.0),
+        );
+
+        let result = affine_transform.transform_point(&Point3::new(1.0, 1.0, 1.0));
+
+        assert_relative_eq!(result, Point3::new(8.0, 1.0, 5.0), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_identity_scale_matches_rigid_transform() {
+        let transform = Transform::new(
+            Utc.timestamp_opt(0, 0).unwrap(),
+            Vector3::new(1.0, -2.0, 3.0),
+            UnitQuaternion::from_euler_angles(0.3, -0.2, 1.1),
+        );
+        let point = Point3::new(4.0, 5.0, -6.0);
+        let expected = transform.isometry().transform_point(&point);
+
+        let result = AffineTransform::from(transform).transform_point(&point);
+
+        assert_relative_eq!(result, expected, epsilon = 1e-12);
+    }
+}
@@ -1,7 +0,0 @@
-use thiserror::Error;
-
-#[derive(Error, Debug)]
-pub enum Error {
-    //#[error("data store disconnected")]
-    //InvalidChannelId(ChannelId),
-}
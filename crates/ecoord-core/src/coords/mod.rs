@@ -1,3 +1,2 @@
-mod error;
 pub mod spherical_point;
 pub mod unit_spherical_point;
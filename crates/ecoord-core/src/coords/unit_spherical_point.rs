@@ -15,19 +15,23 @@ impl<T: Float + Debug + 'static> UnitSphericalPoint3<T> {
     }
 
     pub fn cartesian(&self) -> Point3<T> {
-        let x = self.theta.cos() + self.phi.cos();
-        let y = self.theta.cos() + self.phi.sin();
-        let z = self.phi.sin();
+        let x = self.theta.cos() * self.phi.cos();
+        let y = self.theta.cos() * self.phi.sin();
+        let z = self.theta.sin();
 
         Point3::new(x, y, z)
     }
 }
 impl<T: Float + Debug + RealField> UnitSphericalPoint3<T> {
+    /// Returns the central angle in radians between both points, computed with the haversine
+    /// formula.
     pub fn rad_distance(&self, other: UnitSphericalPoint3<T>) -> T {
-        let phi_diff = (self.phi - other.phi) % T::pi();
-        let theta_diff = (self.theta - other.theta) % T::pi();
+        let two = T::one() + T::one();
+        let haversine = |angle: T| Float::powi(Float::sin(angle / two), 2);
 
-        Float::sqrt(Float::powi(phi_diff, 2) + Float::powi(theta_diff, 2))
+        let a = haversine(other.theta - self.theta)
+            + Float::cos(self.theta) * Float::cos(other.theta) * haversine(other.phi - self.phi);
+        two * Float::asin(Float::sqrt(Float::min(a, T::one())))
     }
 }
 
@@ -57,4 +61,34 @@ mod tests {
         let a = SphericalPoint3::new(1.0, 5.0, 3.0);
         let _b: UnitSphericalPoint3<f64> = a.into();
     }
+
+    #[test]
+    fn test_rad_distance() {
+        use std::f64::consts::{FRAC_PI_2, PI};
+        let point = UnitSphericalPoint3::new(0.3, 0.2);
+
+        let identical = point.rad_distance(point);
+        let orthogonal = UnitSphericalPoint3::new(0.0, 0.0)
+            .rad_distance(UnitSphericalPoint3::new(FRAC_PI_2 + 2.0 * PI, 0.0));
+        let to_pole = UnitSphericalPoint3::new(1.0, 0.0)
+            .rad_distance(UnitSphericalPoint3::new(-2.0, FRAC_PI_2));
+        let antipodal = point.rad_distance(UnitSphericalPoint3::new(0.3 - PI, -0.2));
+
+        assert_eq!(identical, 0.0);
+        assert!((orthogonal - FRAC_PI_2).abs() < 1e-12);
+        assert!((to_pole - FRAC_PI_2).abs() < 1e-12);
+        assert!((antipodal - PI).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cartesian() {
+        let (theta, phi) = (0.4, 2.5);
+        let unit_point = UnitSphericalPoint3::new(phi, theta);
+
+        let result = unit_point.cartesian();
+
+        let expected: Point3<f64> = SphericalPoint3::new(1.0, theta, phi).into();
+        assert!((result.coords.norm() - 1.0).abs() < 1e-12);
+        assert!(distance(&result, &expected) < 1e-12);
+    }
 }
@@ -1,4 +1,5 @@
 use crate::{ChannelId, FrameId, TransformId};
+use chrono::{DateTime, Duration, Utc};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -12,6 +13,12 @@ pub enum Error {
 
     #[error("frame id unknown")]
     InvalidFrameId(FrameId),
+    #[error("transforms form a cycle through frame `{0}`")]
+    CyclicTransformGraph(FrameId),
+    #[error("no path of transforms connects the frames of `{0}`")]
+    NoTransformPath(TransformId),
+    #[error("frame `{0}` must not define both an EPSG code and a WKT string as CRS")]
+    AmbiguousCrs(FrameId),
 
     #[error("data store disconnected")]
     NoChannels(),
@@ -30,6 +37,35 @@ pub enum Error {
         transform_id: TransformId,
     },
 
+    #[error("timestamp is outside the sample range of the transforms {0:?}")]
+    TimestampOutsideSampleRange(Vec<(ChannelId, TransformId)>),
+
+    #[error("quaternion {0:?} must be finite and of unit length")]
+    InvalidQuaternion([f64; 4]),
+
+    #[error("invalid compact transform string `{0}`")]
+    InvalidCompactTransformString(String),
+
+    #[error("differing samples at the same timestamps {timestamps:?} for transform `{transform_id}` of channel `{channel_id}`")]
+    SampleConflict {
+        channel_id: ChannelId,
+        transform_id: TransformId,
+        timestamps: Vec<DateTime<Utc>>,
+    },
+
+    #[error("bin width `{0}` must be positive and representable in nanoseconds")]
+    InvalidBinWidth(Duration),
+
+    #[error("duration `{0}` is not representable in nanoseconds")]
+    DurationOverflow(Duration),
+
+    #[error("differing frame infos for frame `{0}`")]
+    FrameInfoConflict(FrameId),
+    #[error("differing channel infos for channel `{0}`")]
+    ChannelInfoConflict(ChannelId),
+    #[error("differing transform infos for transform `{0}`")]
+    TransformInfoConflict(TransformId),
+
     #[error("collision")]
     ChannelTransformCollisions {
         channel_id: ChannelId,
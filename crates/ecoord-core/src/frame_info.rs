@@ -1,7 +1,12 @@
+use nalgebra::UnitQuaternion;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Dedicated type for an identifier of a frame.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+///
+/// Serialized as a plain string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct FrameId(String);
 
 impl fmt::Display for FrameId {
@@ -31,13 +36,121 @@ impl From<&str> for FrameId {
 /// Additional information for a frame.
 ///
 ///
-#[derive(Default, Debug, Clone, Eq, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct FrameInfo {
     pub crs_epsg: Option<i32>,
+    /// CRS as WKT definition, e.g. for custom local CRSs without EPSG code.
+    pub crs_wkt: Option<String>,
+    /// Rotation from a display convention into the frame, e.g. for data stored in a Z-forward
+    /// camera convention. Only meant for visualization and not applied to the transforms.
+    pub display_rotation: Option<UnitQuaternion<f64>>,
 }
 
 impl FrameInfo {
     pub fn new(crs_epsg: Option<i32>) -> Self {
-        Self { crs_epsg }
+        Self {
+            crs_epsg,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_crs_wkt(mut self, crs_wkt: String) -> Self {
+        self.crs_wkt = Some(crs_wkt);
+        self
+    }
+
+    pub fn with_display_rotation(mut self, display_rotation: UnitQuaternion<f64>) -> Self {
+        self.display_rotation = Some(display_rotation);
+        self
+    }
+
+    pub fn crs_wkt(&self) -> Option<&str> {
+        self.crs_wkt.as_deref()
+    }
+
+    pub fn display_rotation(&self) -> Option<UnitQuaternion<f64>> {
+        self.display_rotation
+    }
+
+    /// Returns true, if the frame's CRS is a known geographic CRS (e.g. EPSG:4326).
+    ///
+    /// Unknown or unset EPSG codes are treated as not geographic. A CRS given only by `crs_wkt`
+    /// is not parsed and therefore also treated as not geographic.
+    pub fn is_geographic(&self) -> bool {
+        self.crs_axis_order() == Some(CrsAxisOrder::LatLon)
+    }
+
+    /// Returns the axis order of the frame's CRS, as defined by the EPSG registry.
+    ///
+    /// Only a small table of common EPSG codes is covered. Returns `None`, if `crs_epsg` is not
+    /// set or the code is unknown, also if the CRS is given by `crs_wkt`.
+    pub fn crs_axis_order(&self) -> Option<CrsAxisOrder> {
+        match self.crs_epsg? {
+            // WGS 84, WGS 84 (3D), ETRS89, ETRS89 (3D), NAD83
+            4326 | 4979 | 4258 | 4937 | 4269 => Some(CrsAxisOrder::LatLon),
+            // DHDN / Gauss-Krüger zones 2 to 5
+            31466..=31469 => Some(CrsAxisOrder::NorthingEasting),
+            // WGS 84 / Pseudo-Mercator
+            3857 => Some(CrsAxisOrder::EastingNorthing),
+            // WGS 84 / UTM zones north and south
+            32601..=32660 | 32701..=32760 => Some(CrsAxisOrder::EastingNorthing),
+            // ETRS89 / UTM zones 28N to 38N
+            25828..=25838 => Some(CrsAxisOrder::EastingNorthing),
+            _ => None,
+        }
+    }
+}
+
+/// Order of the horizontal axes of a coordinate reference system.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CrsAxisOrder {
+    /// Geographic CRS with latitude first
+    LatLon,
+    /// Projected CRS with easting first
+    EastingNorthing,
+    /// Projected CRS with northing first
+    NorthingEasting,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_id_serde_round_trip() {
+        let frame_id = FrameId::from("base_link");
+
+        let serialized = serde_json::to_string(&frame_id).unwrap();
+        let deserialized: FrameId = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(serialized, "\"base_link\"");
+        assert_eq!(deserialized, frame_id);
+    }
+
+    #[test]
+    fn test_geographic_crs() {
+        let frame_info = FrameInfo::new(Some(4326));
+
+        assert!(frame_info.is_geographic());
+        assert_eq!(frame_info.crs_axis_order(), Some(CrsAxisOrder::LatLon));
+    }
+
+    #[test]
+    fn test_projected_crs() {
+        let frame_info = FrameInfo::new(Some(25832));
+
+        assert!(!frame_info.is_geographic());
+        assert_eq!(
+            frame_info.crs_axis_order(),
+            Some(CrsAxisOrder::EastingNorthing)
+        );
+    }
+
+    #[test]
+    fn test_unset_crs() {
+        let frame_info = FrameInfo::new(None);
+
+        assert!(!frame_info.is_geographic());
+        assert_eq!(frame_info.crs_axis_order(), None);
     }
 }
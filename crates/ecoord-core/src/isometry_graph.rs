@@ -1,9 +1,8 @@
 use crate::frame_info::FrameId;
 use crate::transform::TransformId;
 use crate::Error;
-use crate::Error::{InvalidFrameId, MissingTransforms};
+use crate::Error::{InvalidFrameId, MissingTransforms, NoTransformPath};
 use indextree::{Arena, NodeId};
-use itertools::Itertools;
 use nalgebra::Isometry3;
 use std::collections::{HashMap, HashSet};
 
@@ -137,62 +136,112 @@ impl IsometryGraph {
         child_frame_ids.contains(frame_id)
     }
 
+    /// Returns the isometry from the child frame to the frame of the transform id.
+    ///
+    /// If the frame is not an ancestor of the child frame, the transforms are traversed against
+    /// their direction via the common root frame, whereby the traversed transforms are inverted.
     pub fn get_isometry(&self, transform_id: &TransformId) -> Result<Isometry3<f64>, Error> {
+        if let Ok(path_node_ids) = self.get_path_node_ids(transform_id) {
+            return Ok(self.compose_path(&path_node_ids));
+        }
+
+        let (root_frame_id, root_to_frame) = self.get_isometry_from_root(&transform_id.frame_id)?;
+        let (child_root_frame_id, root_to_child_frame) =
+            self.get_isometry_from_root(&transform_id.child_frame_id)?;
+        if root_frame_id != child_root_frame_id {
+            return Err(NoTransformPath(transform_id.clone()));
+        }
+
+        Ok(root_to_frame.inverse() * root_to_child_frame)
+    }
+
+    /// Returns the root frame of the tree containing the frame and the isometry from the frame to
+    /// this root frame.
+    fn get_isometry_from_root(
+        &self,
+        frame_id: &FrameId,
+    ) -> Result<(FrameId, Isometry3<f64>), Error> {
+        if !self.get_frame_ids().contains(frame_id) {
+            return Err(InvalidFrameId(frame_id.clone()));
+        }
+        let Some((_, child_node_id)) = self
+            .transform_id_to_node_id_map
+            .iter()
+            .find(|(t, _)| &t.child_frame_id == frame_id)
+        else {
+            return Ok((frame_id.clone(), Isometry3::identity()));
+        };
+
+        let path_node_ids: Vec<NodeId> = child_node_id.ancestors(&self.arena).collect();
+        let root_frame_id = self.node_id_to_transform_id_map[path_node_ids.last().unwrap()]
+            .frame_id
+            .clone();
+        Ok((root_frame_id, self.compose_path(&path_node_ids)))
+    }
+
+    /// Composes the isometries of the path, which starts at the node of the child frame.
+    fn compose_path(&self, path_node_ids: &[NodeId]) -> Isometry3<f64> {
+        path_node_ids
+            .iter()
+            .map(|n| self.arena.get(*n).unwrap().get())
+            .fold(Isometry3::identity(), |acc, current_node| {
+                current_node * acc
+            })
+    }
+
+    /// Returns the number of transforms on the path from the frame to the child frame.
+    ///
+    /// Only paths along the direction of the transforms are considered.
+    pub fn hop_count(&self, transform_id: &TransformId) -> Result<usize, Error> {
+        let path_node_ids = self.get_path_node_ids(transform_id)?;
+        Ok(path_node_ids.len())
+    }
+
+    /// Returns the summed translation lengths of all transforms on the path from the frame to the
+    /// child frame.
+    pub fn path_length(&self, transform_id: &TransformId) -> Result<f64, Error> {
+        let path_node_ids = self.get_path_node_ids(transform_id)?;
+
+        let length = path_node_ids
+            .iter()
+            .map(|n| self.arena.get(*n).unwrap().get().translation.vector.norm())
+            .sum();
+        Ok(length)
+    }
+
+    /// Returns the node ids on the path, starting at the node of the child frame and walking up
+    /// to the node of the requested frame.
+    ///
+    /// Fails, if the frame is not an ancestor of the child frame.
+    fn get_path_node_ids(&self, transform_id: &TransformId) -> Result<Vec<NodeId>, Error> {
         if !self.contains_parent_frame_id(&transform_id.frame_id) {
             return Err(InvalidFrameId(transform_id.frame_id.clone()));
         }
         if !self.contains_child_frame_id(&transform_id.child_frame_id) {
             return Err(InvalidFrameId(transform_id.child_frame_id.clone()));
         }
-        // assert!(
-        //     self.transform_id_to_node_id_map
-        //         .keys()
-        //         .any(|k| k.frame_id == transform_id.frame_id),
-        //     "Transform graph must contain a transform with frame_id: {}",
-        //     &transform_id.frame_id
-        // );
-        // assert!(
-        //     self.transform_id_to_node_id_map
-        //         .keys()
-        //         .any(|k| k.child_frame_id == transform_id.child_frame_id),
-        //     "Transform graph must contain a transform with child_frame_id: {}",
-        //     &transform_id.child_frame_id
-        // );
 
         let child_node_id = self
-            //.child_frame_id_to_node_id_map
             .transform_id_to_node_id_map
             .iter()
             .find(|(t, _)| t.child_frame_id == transform_id.child_frame_id)
             .ok_or(InvalidFrameId(transform_id.child_frame_id.clone()))?
             .1;
 
-        let mut isometry: Isometry3<f64> = Isometry3::identity();
-        let ancestors = child_node_id.ancestors(&self.arena);
-        // let _anc_pr = ancestors.clone().map(|a| a.to_string()).join(" ");
-        // println!("ancestor ids: {}", anc_pr);
-        // let mut selected_nodes: Vec<&Transform> = vec![];
-        for current_ancestor_node_id in ancestors {
-            let current_node = self.arena.get(current_ancestor_node_id).unwrap().get();
+        let mut path_node_ids: Vec<NodeId> = Vec::new();
+        for current_ancestor_node_id in child_node_id.ancestors(&self.arena) {
+            path_node_ids.push(current_ancestor_node_id);
+
             let current_transform_id = self
                 .node_id_to_transform_id_map
                 .get(&current_ancestor_node_id)
                 .unwrap();
-
-            //println!(
-            //    "current node: frame_id={} child_frame_id={}",
-            //    &current_transform_id.frame_id, &current_transform_id.child_frame_id
-            //);
-            isometry = current_node * isometry;
-            // println!("isometry after: {}", isometry);
-
             if current_transform_id.frame_id == transform_id.frame_id {
-                break;
+                return Ok(path_node_ids);
             }
         }
 
-        // dbg!("isometry final: {}", isometry);
-        Ok(isometry)
+        Err(NoTransformPath(transform_id.clone()))
     }
 }
 
@@ -201,7 +250,7 @@ mod test_graph {
     use crate::isometry_graph::IsometryGraph;
     use crate::{FrameId, TransformId};
 
-    use nalgebra::{Isometry3, Translation3, UnitQuaternion};
+    use nalgebra::{Isometry3, Point3, Translation3, UnitQuaternion};
     use std::collections::HashMap;
 
     #[test]
@@ -237,4 +286,89 @@ mod test_graph {
 
         assert_eq!(result.translation, Translation3::new(50.0, 0.0, 0.0));
     }
+
+    #[test]
+    fn test_hop_count_and_path_length() {
+        let mut isometry_transforms: HashMap<TransformId, Isometry3<f64>> = HashMap::new();
+        isometry_transforms.insert(
+            TransformId::new(FrameId::from("map"), FrameId::from("odom")),
+            Isometry3::from_parts(Translation3::new(3.0, 4.0, 0.0), UnitQuaternion::default()),
+        );
+        isometry_transforms.insert(
+            TransformId::new(FrameId::from("odom"), FrameId::from("base_link")),
+            Isometry3::from_parts(
+                Translation3::new(0.0, 2.0, 0.0),
+                UnitQuaternion::from_euler_angles(0.0, 0.0, std::f64::consts::FRAC_PI_2),
+            ),
+        );
+        isometry_transforms.insert(
+            TransformId::new(FrameId::from("base_link"), FrameId::from("lidar")),
+            Isometry3::from_parts(Translation3::new(0.0, 0.0, 1.0), UnitQuaternion::default()),
+        );
+        let isometry_graph = IsometryGraph::new(isometry_transforms).unwrap();
+        let transform_id = TransformId::new(FrameId::from("map"), FrameId::from("lidar"));
+
+        assert_eq!(isometry_graph.hop_count(&transform_id).unwrap(), 3);
+        assert_eq!(isometry_graph.path_length(&transform_id).unwrap(), 8.0);
+        assert_eq!(
+            isometry_graph
+                .hop_count(&TransformId::new(
+                    FrameId::from("odom"),
+                    FrameId::from("lidar")
+                ))
+                .unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_get_isometry_against_direction() {
+        let mut isometry_transforms: HashMap<TransformId, Isometry3<f64>> = HashMap::new();
+        isometry_transforms.insert(
+            TransformId::new(FrameId::from("map"), FrameId::from("base_link")),
+            Isometry3::from_parts(
+                Translation3::new(10.0, 5.0, 0.0),
+                UnitQuaternion::from_euler_angles(0.0, 0.0, std::f64::consts::FRAC_PI_2),
+            ),
+        );
+        isometry_transforms.insert(
+            TransformId::new(FrameId::from("base_link"), FrameId::from("lidar")),
+            Isometry3::from_parts(
+                Translation3::new(1.0, 0.0, 2.0),
+                UnitQuaternion::from_euler_angles(0.1, 0.0, 0.0),
+            ),
+        );
+        isometry_transforms.insert(
+            TransformId::new(FrameId::from("base_link"), FrameId::from("camera")),
+            Isometry3::from_parts(Translation3::new(0.0, 1.0, 0.0), UnitQuaternion::default()),
+        );
+        let isometry_graph = IsometryGraph::new(isometry_transforms).unwrap();
+
+        let map_to_lidar = isometry_graph
+            .get_isometry(&TransformId::new(
+                FrameId::from("map"),
+                FrameId::from("lidar"),
+            ))
+            .unwrap();
+        let lidar_to_map = isometry_graph
+            .get_isometry(&TransformId::new(
+                FrameId::from("lidar"),
+                FrameId::from("map"),
+            ))
+            .unwrap();
+        let camera_to_lidar = isometry_graph
+            .get_isometry(&TransformId::new(
+                FrameId::from("camera"),
+                FrameId::from("lidar"),
+            ))
+            .unwrap();
+
+        let point = Point3::new(1.0, 2.0, 3.0);
+        let round_trip = lidar_to_map * map_to_lidar;
+        assert!((round_trip * point - point).norm() < 1e-12);
+        // the lidar origin expressed in the map frame
+        assert!((map_to_lidar * Point3::origin() - Point3::new(10.0, 6.0, 2.0)).norm() < 1e-12);
+        // the lidar origin expressed in the camera frame
+        assert!((camera_to_lidar * Point3::origin() - Point3::new(1.0, -1.0, 2.0)).norm() < 1e-12);
+    }
 }
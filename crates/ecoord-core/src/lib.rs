@@ -1,3 +1,4 @@
+mod affine_transform;
 mod channel_info;
 mod coords;
 mod error;
@@ -18,12 +19,21 @@ pub use crate::transform::TransformId;
 #[doc(inline)]
 pub use crate::transform::Transform;
 
+#[doc(inline)]
+pub use crate::transform::GIMBAL_LOCK_EPSILON;
+
+#[doc(inline)]
+pub use crate::affine_transform::AffineTransform;
+
 #[doc(inline)]
 pub use crate::frame_info::FrameId;
 
 #[doc(inline)]
 pub use crate::frame_info::FrameInfo;
 
+#[doc(inline)]
+pub use crate::frame_info::CrsAxisOrder;
+
 #[doc(inline)]
 pub use crate::channel_info::ChannelId;
 
@@ -37,7 +47,10 @@ pub use crate::transform_info::TransformInfo;
 pub use crate::transform_info::InterpolationMethod;
 
 #[doc(inline)]
-pub use crate::ops::merge::merge;
+pub use crate::transform_info::RotationInterpolation;
+
+#[doc(inline)]
+pub use crate::ops::merge::{merge, merge_with_policy, MergePolicy};
 
 #[doc(inline)]
 pub use crate::coords::spherical_point::SphericalPoint3;
@@ -5,27 +5,68 @@ use crate::{
 
 use crate::error::Error;
 
-use crate::Error::ChannelTransformCollisions;
+use crate::Error::{
+    ChannelInfoConflict, ChannelTransformCollisions, FrameInfoConflict, SampleConflict,
+    TransformInfoConflict,
+};
+use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Policy for resolving [ChannelId] and [TransformId] combinations contained in multiple
+/// [ReferenceFrames] during a merge.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum MergePolicy {
+    /// Keeps the transforms of the first reference frames containing the combination.
+    KeepExisting,
+    /// Keeps the transforms of the last reference frames containing the combination.
+    Overwrite,
+    /// Combines the samples of all reference frames containing the combination.
+    ///
+    /// Identical samples with the same timestamp are kept once, while differing samples with the
+    /// same timestamp fail with [Error::SampleConflict].
+    Concatenate,
+    /// Fails with [Error::ChannelTransformCollisions].
+    #[default]
+    Error,
+}
 
 /// Merges a list of reference frame systems to a single reference frame system.
 /// Requires unique [ChannelId] and [TransformId] combinations across the input [ReferenceFrames].
 pub fn merge(reference_frames: &[ReferenceFrames]) -> Result<ReferenceFrames, Error> {
-    let all_combinations: HashSet<&(ChannelId, TransformId)> = reference_frames
-        .iter()
-        .flat_map(|r| r.transforms.keys())
-        .collect();
-    for current_combination in all_combinations {
-        let number_of_occurrences = reference_frames
+    merge_with_policy(reference_frames, MergePolicy::Error)
+}
+
+/// Merges a list of reference frame systems to a single reference frame system, whereby
+/// duplicate [ChannelId] and [TransformId] combinations are resolved according to the `policy`.
+///
+/// The channel and transform infos follow the same policy, whereby [MergePolicy::Concatenate] and
+/// [MergePolicy::Error] fail with [Error::ChannelInfoConflict] and [Error::TransformInfoConflict]
+/// for differing infos of the same id. Frame infos are unioned, whereby set
+/// fields are preferred over unset ones and the CRS is taken as a whole from a single input.
+/// Differing set fields are resolved by the policy, whereby [MergePolicy::Concatenate] and
+/// [MergePolicy::Error] fail with [Error::FrameInfoConflict].
+pub fn merge_with_policy(
+    reference_frames: &[ReferenceFrames],
+    policy: MergePolicy,
+) -> Result<ReferenceFrames, Error> {
+    if policy == MergePolicy::Error {
+        let all_combinations: HashSet<&(ChannelId, TransformId)> = reference_frames
             .iter()
-            .filter(|r| r.transforms.keys().contains(current_combination))
-            .count();
-        if number_of_occurrences > 1 {
-            return Err(ChannelTransformCollisions {
-                channel_id: current_combination.0.clone(),
-                transform_id: current_combination.1.clone(),
-            });
+            .flat_map(|r| r.transforms.keys())
+            .collect();
+        for current_combination in all_combinations {
+            let number_of_occurrences = reference_frames
+                .iter()
+                .filter(|r| r.transforms.keys().contains(current_combination))
+                .count();
+            if number_of_occurrences > 1 {
+                return Err(ChannelTransformCollisions {
+                    channel_id: current_combination.0.clone(),
+                    transform_id: current_combination.1.clone(),
+                });
+            }
         }
     }
 
@@ -35,31 +76,82 @@ pub fn merge(reference_frames: &[ReferenceFrames]) -> Result<ReferenceFrames, Er
     let mut all_transform_infos: HashMap<TransformId, TransformInfo> = HashMap::new();
 
     for current_reference_frame in reference_frames {
-        current_reference_frame.transforms.iter().for_each(|t| {
-            all_transforms
-                .entry(t.0.clone())
-                .or_default()
-                .append(&mut t.1.clone())
-        });
-
-        current_reference_frame.frame_info.iter().for_each(|t| {
-            // TODO: error, if different frame infos are available
-            all_frame_infos.insert(t.0.clone(), t.1.clone());
-        });
-
-        current_reference_frame.channel_info.iter().for_each(|t| {
-            // TODO: error, if different channel infos are available
-            all_channel_infos.insert(t.0.clone(), t.1.clone());
-        });
-
-        current_reference_frame.transform_info.iter().for_each(|t| {
-            // TODO: error, if different channel infos are available
-            all_transform_infos.insert(t.0.clone(), t.1.clone());
-        });
+        for (current_id, current_transforms) in &current_reference_frame.transforms {
+            if policy == MergePolicy::Concatenate {
+                all_transforms
+                    .entry(current_id.clone())
+                    .or_default()
+                    .extend_from_slice(current_transforms);
+            } else {
+                insert_with_policy(
+                    &mut all_transforms,
+                    current_id,
+                    current_transforms,
+                    policy,
+                    |(channel_id, transform_id)| ChannelTransformCollisions {
+                        channel_id: channel_id.clone(),
+                        transform_id: transform_id.clone(),
+                    },
+                )?;
+            }
+        }
+
+        for (frame_id, current_frame_info) in &current_reference_frame.frame_info {
+            let frame_info = all_frame_infos.entry(frame_id.clone()).or_default();
+            let crs = |f: &FrameInfo| {
+                (f.crs_epsg.is_some() || f.crs_wkt.is_some())
+                    .then(|| (f.crs_epsg, f.crs_wkt.clone()))
+            };
+
+            let mut merged_crs = crs(frame_info);
+            merge_field(&mut merged_crs, crs(current_frame_info), policy, frame_id)?;
+            (frame_info.crs_epsg, frame_info.crs_wkt) = merged_crs.unwrap_or_default();
+            merge_field(
+                &mut frame_info.display_rotation,
+                current_frame_info.display_rotation,
+                policy,
+                frame_id,
+            )?;
+        }
+
+        for (channel_id, current_channel_info) in &current_reference_frame.channel_info {
+            insert_with_policy(
+                &mut all_channel_infos,
+                channel_id,
+                current_channel_info,
+                policy,
+                |c| ChannelInfoConflict(c.clone()),
+            )?;
+        }
+
+        for (transform_id, current_transform_info) in &current_reference_frame.transform_info {
+            insert_with_policy(
+                &mut all_transform_infos,
+                transform_id,
+                current_transform_info,
+                policy,
+                |t| TransformInfoConflict(t.clone()),
+            )?;
+        }
     }
 
-    for current_transform in all_transforms.values_mut() {
-        current_transform.sort_by_key(|t| t.timestamp.timestamp_nanos());
+    for (current_id, current_transforms) in all_transforms.iter_mut() {
+        current_transforms.sort_by_key(|t| t.timestamp);
+
+        let conflicting_timestamps: Vec<DateTime<Utc>> = current_transforms
+            .windows(2)
+            .filter(|t| t[0].timestamp == t[1].timestamp && t[0] != t[1])
+            .map(|t| t[0].timestamp)
+            .dedup()
+            .collect();
+        if !conflicting_timestamps.is_empty() {
+            return Err(SampleConflict {
+                channel_id: current_id.0.clone(),
+                transform_id: current_id.1.clone(),
+                timestamps: conflicting_timestamps,
+            });
+        }
+        current_transforms.dedup();
     }
 
     ReferenceFrames::new(
@@ -69,3 +161,216 @@ pub fn merge(reference_frames: &[ReferenceFrames]) -> Result<ReferenceFrames, Er
         all_transform_infos,
     )
 }
+
+/// Sets an optional frame info field according to the policy, if the new value is set.
+fn merge_field<T: PartialEq>(
+    existing: &mut Option<T>,
+    value: Option<T>,
+    policy: MergePolicy,
+    frame_id: &FrameId,
+) -> Result<(), Error> {
+    let Some(value) = value else {
+        return Ok(());
+    };
+    match (existing.as_ref(), policy) {
+        (None, _) | (Some(_), MergePolicy::Overwrite) => *existing = Some(value),
+        (Some(_), MergePolicy::KeepExisting) => {}
+        (Some(e), MergePolicy::Concatenate | MergePolicy::Error) => {
+            if *e != value {
+                return Err(FrameInfoConflict(frame_id.clone()));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Inserts the value according to the policy, whereby [MergePolicy::Concatenate] and
+/// [MergePolicy::Error] fail with the `conflict` error for a differing existing value.
+fn insert_with_policy<K: Clone + Eq + Hash, V: Clone + PartialEq>(
+    map: &mut HashMap<K, V>,
+    key: &K,
+    value: &V,
+    policy: MergePolicy,
+    conflict: impl FnOnce(&K) -> Error,
+) -> Result<(), Error> {
+    match (map.get(key), policy) {
+        (None, _) | (Some(_), MergePolicy::Overwrite) => {
+            map.insert(key.clone(), value.clone());
+        }
+        (Some(_), MergePolicy::KeepExisting) => {}
+        (Some(existing), MergePolicy::Concatenate | MergePolicy::Error) => {
+            if existing != value {
+                return Err(conflict(key));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InterpolationMethod;
+    use chrono::TimeZone;
+    use nalgebra::{UnitQuaternion, Vector3};
+
+    fn reference_frames_with_sample(x: f64, crs_epsg: Option<i32>) -> ReferenceFrames {
+        let transform_id = TransformId::new(FrameId::from("map"), FrameId::from("base"));
+        let transform = Transform::new(
+            Utc.timestamp_opt(0, 0).unwrap(),
+            Vector3::new(x, 0.0, 0.0),
+            UnitQuaternion::identity(),
+        );
+        ReferenceFrames::new(
+            HashMap::from([(
+                (ChannelId::from("calibration"), transform_id),
+                vec![transform],
+            )]),
+            HashMap::from([(FrameId::from("map"), FrameInfo::new(crs_epsg))]),
+            HashMap::new(),
+            HashMap::new(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge_with_policy() {
+        let reference_frames = [
+            reference_frames_with_sample(1.0, Some(25832)),
+            reference_frames_with_sample(2.0, None),
+        ];
+        let sample_x =
+            |r: &ReferenceFrames| r.transforms().values().next().unwrap()[0].translation.x;
+
+        let kept = merge_with_policy(&reference_frames, MergePolicy::KeepExisting).unwrap();
+        let overwritten = merge_with_policy(&reference_frames, MergePolicy::Overwrite).unwrap();
+        let collision = merge_with_policy(&reference_frames, MergePolicy::Error);
+
+        assert_eq!(sample_x(&kept), 1.0);
+        assert_eq!(sample_x(&overwritten), 2.0);
+        assert_eq!(
+            overwritten.frame_info()[&FrameId::from("map")].crs_epsg,
+            Some(25832)
+        );
+        assert!(matches!(collision, Err(ChannelTransformCollisions { .. })));
+    }
+
+    #[test]
+    fn test_merge_concatenate() {
+        let transform_id = TransformId::new(FrameId::from("world"), FrameId::from("base"));
+        let reference_frames_from_samples = |samples: &[(i64, f64)]| {
+            let transforms = samples
+                .iter()
+                .map(|(sec, x)| {
+                    Transform::new(
+                        Utc.timestamp_opt(*sec, 0).unwrap(),
+                        Vector3::new(*x, 0.0, 0.0),
+                        UnitQuaternion::identity(),
+                    )
+                })
+                .collect();
+            ReferenceFrames::new(
+                HashMap::from([((ChannelId::from("slam"), transform_id.clone()), transforms)]),
+                HashMap::new(),
+                HashMap::new(),
+                HashMap::new(),
+            )
+            .unwrap()
+        };
+        let reference_frames = reference_frames_from_samples(&[(0, 0.0), (1, 1.0), (2, 2.0)]);
+        let later = reference_frames_from_samples(&[(2, 2.0), (3, 3.0)]);
+        let conflicting = reference_frames_from_samples(&[(1, 1.5), (2, 2.0), (3, 3.5)]);
+
+        let merged = merge_with_policy(
+            &[reference_frames.clone(), later.clone()],
+            MergePolicy::Concatenate,
+        )
+        .unwrap();
+        let conflict = merge_with_policy(
+            &[reference_frames, later, conflicting],
+            MergePolicy::Concatenate,
+        );
+
+        assert_eq!(merged.total_sample_count(), 4);
+        match conflict {
+            Err(SampleConflict { timestamps, .. }) => assert_eq!(
+                timestamps,
+                vec![
+                    Utc.timestamp_opt(1, 0).unwrap(),
+                    Utc.timestamp_opt(3, 0).unwrap()
+                ]
+            ),
+            _ => panic!("expected a sample conflict"),
+        }
+    }
+
+    #[test]
+    fn test_merge_frame_info_crs() {
+        let with_frame_info = |x: f64, frame_info: FrameInfo| {
+            let mut reference_frames = reference_frames_with_sample(x, None);
+            reference_frames
+                .frame_info
+                .insert(FrameId::from("map"), frame_info);
+            reference_frames
+        };
+        let crs_wkt = r#"ENGCRS["site grid"]"#.to_string();
+        let reference_frames = [
+            with_frame_info(1.0, FrameInfo::new(Some(25832))),
+            with_frame_info(2.0, FrameInfo::new(None).with_crs_wkt(crs_wkt.clone())),
+        ];
+        let crs = |r: &ReferenceFrames| {
+            let frame_info = &r.frame_info()[&FrameId::from("map")];
+            (frame_info.crs_epsg, frame_info.crs_wkt.clone())
+        };
+
+        let kept = merge_with_policy(&reference_frames, MergePolicy::KeepExisting).unwrap();
+        let overwritten = merge_with_policy(&reference_frames, MergePolicy::Overwrite).unwrap();
+        let conflict = merge_with_policy(&reference_frames, MergePolicy::Concatenate);
+
+        assert_eq!(crs(&kept), (Some(25832), None));
+        assert_eq!(crs(&overwritten), (None, Some(crs_wkt)));
+        assert!(matches!(conflict, Err(FrameInfoConflict(_))));
+    }
+
+    #[test]
+    fn test_merge_transform_info_conflict() {
+        let transform_id = TransformId::new(FrameId::from("map"), FrameId::from("base"));
+        let with_transform_info = |x: f64, interpolation_method: InterpolationMethod| {
+            let mut reference_frames = reference_frames_with_sample(x, None);
+            reference_frames.transform_info.insert(
+                transform_id.clone(),
+                TransformInfo::new(Some(interpolation_method), None),
+            );
+            reference_frames
+        };
+        let reference_frames = [
+            with_transform_info(1.0, InterpolationMethod::Step),
+            with_transform_info(2.0, InterpolationMethod::Linear),
+        ];
+        let interpolation_method =
+            |r: &ReferenceFrames| r.transform_info()[&transform_id].interpolation_method;
+
+        let kept = merge_with_policy(&reference_frames, MergePolicy::KeepExisting).unwrap();
+        let overwritten = merge_with_policy(&reference_frames, MergePolicy::Overwrite).unwrap();
+        let concatenated = merge_with_policy(
+            &[
+                with_transform_info(1.0, InterpolationMethod::Step),
+                with_transform_info(1.0, InterpolationMethod::Step),
+            ],
+            MergePolicy::Concatenate,
+        )
+        .unwrap();
+        let conflict = merge_with_policy(&reference_frames, MergePolicy::Concatenate);
+
+        assert_eq!(interpolation_method(&kept), Some(InterpolationMethod::Step));
+        assert_eq!(
+            interpolation_method(&overwritten),
+            Some(InterpolationMethod::Linear)
+        );
+        assert_eq!(
+            interpolation_method(&concatenated),
+            Some(InterpolationMethod::Step)
+        );
+        assert!(matches!(conflict, Err(TransformInfoConflict(t)) if t == transform_id));
+    }
+}
@@ -6,14 +6,22 @@ use crate::isometry_graph::IsometryGraph;
 use crate::ops::filter::filter_by_channel;
 use crate::transform::TransformId;
 use crate::transform_info::TransformInfo;
+use crate::utils::transform_list_utils::{
+    covers_timestamp, get_interval_containing, get_intervals,
+};
 use crate::utils::transforms_interpolation::interpolate_transforms;
 
-use crate::Error::{InvalidTransformId, MissingTransforms, NoChannels};
-use crate::{InterpolationMethod, Transform};
-use chrono::{DateTime, Utc};
+use crate::Error::{
+    AmbiguousCrs, CyclicTransformGraph, DurationOverflow, InvalidBinWidth, InvalidFrameId,
+    InvalidTransformId, MissingTransforms, NoChannels, TimestampOutsideSampleRange,
+};
+use crate::{InterpolationMethod, RotationInterpolation, Transform};
+use chrono::{DateTime, Duration, Utc};
 use itertools::Itertools;
-use nalgebra::Isometry3;
-use std::collections::{HashMap, HashSet};
+use nalgebra::{distance, Isometry3, Point3, Vector3};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 
 use std::vec;
 
@@ -49,7 +57,7 @@ impl ReferenceFrames {
             for (current_id, current_transform) in &transforms {
                 if !current_transform
                     .windows(2)
-                    .all(|t| t[0].timestamp.timestamp_nanos() < t[1].timestamp.timestamp_nanos())
+                    .all(|t| t[0].timestamp < t[1].timestamp)
                 {
                     return Err(TransformsNotSorted {
                         channel_id: current_id.0.clone(),
@@ -59,6 +67,13 @@ impl ReferenceFrames {
             }
         }
 
+        if let Some((frame_id, _)) = frame_info
+            .iter()
+            .find(|(_, f)| f.crs_epsg.is_some() && f.crs_wkt.is_some())
+        {
+            return Err(AmbiguousCrs(frame_id.clone()));
+        }
+
         // sorting the transform vectors by time
         let mut sorted_transforms: HashMap<(ChannelId, TransformId), Vec<Transform>> =
             HashMap::new();
@@ -87,18 +102,28 @@ impl ReferenceFrames {
         //    assert_eq!(a.child_frame_id, b.parent_frame_id, "Child frame '{}' does not fit to parent frame '{}' of the following transform", a.child_frame_id, b.parent_frame_id);
         //}
 
-        Ok(Self {
+        let reference_frames = Self {
             transforms: sorted_transforms,
             frame_info,
             channel_info,
             transform_info,
-        })
+        };
+        if let Some(frame_id) = reference_frames.find_cycle() {
+            return Err(CyclicTransformGraph(frame_id));
+        }
+
+        Ok(reference_frames)
     }
 
     pub fn is_empty(&self) -> bool {
         self.transforms.is_empty()
     }
 
+    /// Returns the number of transform samples summed over all channels and transforms.
+    pub fn total_sample_count(&self) -> usize {
+        self.transforms.values().map(|t| t.len()).sum()
+    }
+
     pub fn frame_info(&self) -> &HashMap<FrameId, FrameInfo> {
         &self.frame_info
     }
@@ -172,6 +197,100 @@ impl ReferenceFrames {
         Ok(reference_frame)
     }
 
+    /// Creates a subset containing only the kept frames.
+    ///
+    /// Transforms between two kept frames are taken over unchanged. Paths through dropped frames
+    /// are collapsed into a single transform from the closest kept ancestor, which is resolved at
+    /// the timestamp and assigned to the channel of the transform ending in the kept frame.
+    /// Without a timestamp, collapsed paths must consist of static transforms only.
+    ///
+    /// * `kept_frame_ids` - Frames to keep.
+    /// * `timestamp` - Timestamp to choose for interpolating collapsed time-dependent transforms.
+    pub fn retain_frames(
+        &self,
+        kept_frame_ids: &HashSet<FrameId>,
+        timestamp: &Option<DateTime<Utc>>,
+    ) -> Result<ReferenceFrames, Error> {
+        let parent_frame_ids: HashMap<FrameId, FrameId> = self
+            .get_transform_ids()
+            .into_iter()
+            .map(|t| (t.child_frame_id, t.frame_id))
+            .collect();
+        let get_kept_ancestor = |frame_id: &FrameId| -> Option<FrameId> {
+            let mut current_frame_id = parent_frame_ids.get(frame_id)?;
+            while !kept_frame_ids.contains(current_frame_id) {
+                current_frame_id = parent_frame_ids.get(current_frame_id)?;
+            }
+            Some(current_frame_id.clone())
+        };
+
+        let mut transform_graph: Option<IsometryGraph> = None;
+        let mut all_transforms: HashMap<(ChannelId, TransformId), Vec<Transform>> = HashMap::new();
+        for ((channel_id, transform_id), transforms) in &self.transforms {
+            if !kept_frame_ids.contains(&transform_id.child_frame_id) {
+                continue;
+            }
+            if kept_frame_ids.contains(&transform_id.frame_id) {
+                all_transforms.insert(
+                    (channel_id.clone(), transform_id.clone()),
+                    transforms.clone(),
+                );
+                continue;
+            }
+            let Some(ancestor_frame_id) = get_kept_ancestor(&transform_id.frame_id) else {
+                continue;
+            };
+
+            let collapsed_transform_id =
+                TransformId::new(ancestor_frame_id, transform_id.child_frame_id.clone());
+            let transform_graph = match transform_graph {
+                Some(ref transform_graph) => transform_graph,
+                None => transform_graph.insert(self.derive_transform_graph(&None, timestamp)?),
+            };
+            let isometry = transform_graph.get_isometry(&collapsed_transform_id)?;
+            let collapsed_timestamp =
+                timestamp.unwrap_or_else(|| transforms.first().unwrap().timestamp);
+            all_transforms.insert(
+                (channel_id.clone(), collapsed_transform_id),
+                vec![Transform::from(collapsed_timestamp, isometry)],
+            );
+        }
+
+        let selected_channel_ids: HashSet<&ChannelId> =
+            all_transforms.keys().map(|(c, _)| c).collect();
+        let selected_transform_ids: HashSet<&TransformId> =
+            all_transforms.keys().map(|(_, t)| t).collect();
+
+        let all_frame_info: HashMap<FrameId, FrameInfo> = self
+            .frame_info
+            .iter()
+            .filter(|(i, _)| kept_frame_ids.contains(i))
+            .map(|(i, f)| (i.clone(), f.clone()))
+            .collect();
+
+        let all_channel_info: HashMap<ChannelId, ChannelInfo> = self
+            .channel_info
+            .iter()
+            .filter(|(i, _)| selected_channel_ids.contains(i))
+            .map(|(i, c)| (i.clone(), c.clone()))
+            .collect();
+
+        let all_transform_info: HashMap<TransformId, TransformInfo> = self
+            .transform_info
+            .iter()
+            .filter(|(i, _)| selected_transform_ids.contains(i))
+            .map(|(i, c)| (i.clone(), c.clone()))
+            .collect();
+
+        let reference_frame = ReferenceFrames::new(
+            all_transforms,
+            all_frame_info,
+            all_channel_info,
+            all_transform_info,
+        )?;
+        Ok(reference_frame)
+    }
+
     pub fn get_timed_subset(&self, timestamp: &DateTime<Utc>) -> Result<ReferenceFrames, Error> {
         let all_transforms: HashMap<(ChannelId, TransformId), Vec<Transform>> = self
             .transforms
@@ -180,8 +299,15 @@ impl ReferenceFrames {
                 let interpolation_method = self
                     .get_interpolation_method(transform_id)
                     .unwrap_or_default();
-                let isometry =
-                    interpolate_transforms(transforms, &Some(*timestamp), interpolation_method);
+                let rotation_interpolation = self
+                    .get_rotation_interpolation(transform_id)
+                    .unwrap_or_default();
+                let isometry = interpolate_transforms(
+                    transforms,
+                    &Some(*timestamp),
+                    interpolation_method,
+                    rotation_interpolation,
+                );
 
                 isometry.map(|i| {
                     (
@@ -198,7 +324,7 @@ impl ReferenceFrames {
             .map(|k| {
                 (
                     k.clone(),
-                    TransformInfo::new(Some(InterpolationMethod::Step)),
+                    TransformInfo::new(Some(InterpolationMethod::Step), None),
                 )
             })
             .collect();
@@ -212,6 +338,151 @@ impl ReferenceFrames {
         Ok(reference_frame)
     }
 
+    /// Same as [`ReferenceFrames::get_timed_subset`], but fails instead of holding the first or
+    /// last sample, if the timestamp is outside the sample range of a time-dependent transform.
+    ///
+    /// The error lists all affected channel and transform ids in sorted order.
+    pub fn get_timed_subset_strict(
+        &self,
+        timestamp: &DateTime<Utc>,
+    ) -> Result<ReferenceFrames, Error> {
+        let uncovered_ids: Vec<(ChannelId, TransformId)> = self
+            .transforms
+            .iter()
+            .filter(|(_, transforms)| !covers_timestamp(transforms, timestamp))
+            .map(|(id, _)| id.clone())
+            .sorted()
+            .collect();
+        if !uncovered_ids.is_empty() {
+            return Err(TimestampOutsideSampleRange(uncovered_ids));
+        }
+
+        self.get_timed_subset(timestamp)
+    }
+
+    /// Creates a subset with the transforms resolved at the timestamp, skipping those which
+    /// cannot be resolved.
+    ///
+    /// Time-dependent transforms, whose samples do not span the timestamp or which fail to
+    /// interpolate, are skipped instead of failing the whole subset. Returns the subset together
+    /// with the sorted ids of the skipped transforms.
+    pub fn get_timed_subset_lenient(
+        &self,
+        timestamp: &DateTime<Utc>,
+    ) -> Result<(ReferenceFrames, Vec<(ChannelId, TransformId)>), Error> {
+        let mut all_transforms: HashMap<(ChannelId, TransformId), Vec<Transform>> = HashMap::new();
+        let mut skipped_ids: Vec<(ChannelId, TransformId)> = Vec::new();
+        for ((channel_id, transform_id), transforms) in &self.transforms {
+            let isometry = if transforms.is_empty() || !covers_timestamp(transforms, timestamp) {
+                None
+            } else {
+                interpolate_transforms(
+                    transforms,
+                    &Some(*timestamp),
+                    self.get_interpolation_method(transform_id)
+                        .unwrap_or_default(),
+                    self.get_rotation_interpolation(transform_id)
+                        .unwrap_or_default(),
+                )
+                .ok()
+            };
+
+            match isometry {
+                Some(isometry) => {
+                    all_transforms.insert(
+                        (channel_id.clone(), transform_id.clone()),
+                        vec![Transform::from(*timestamp, isometry)],
+                    );
+                }
+                None => skipped_ids.push((channel_id.clone(), transform_id.clone())),
+            }
+        }
+        skipped_ids.sort();
+
+        let all_transform_info = self
+            .transform_info
+            .keys()
+            .filter(|k| all_transforms.keys().any(|(_, t)| t == *k))
+            .map(|k| {
+                (
+                    k.clone(),
+                    TransformInfo::new(Some(InterpolationMethod::Step), None),
+                )
+            })
+            .collect();
+
+        let reference_frame = ReferenceFrames::new(
+            all_transforms,
+            self.frame_info.clone(),
+            self.channel_info.clone(),
+            all_transform_info,
+        )?;
+        Ok((reference_frame, skipped_ids))
+    }
+
+    /// Returns the sample rate in samples per second for each channel and transform.
+    ///
+    /// The rate is computed as `(count - 1) / time span`. Transforms with a single sample are
+    /// omitted. Fails with [`Error::DurationOverflow`], if a time span is not representable in
+    /// nanoseconds, and with [`Error::TransformsNotSorted`], if a time span is not positive.
+    pub fn get_sample_rates(&self) -> Result<HashMap<(ChannelId, TransformId), f64>, Error> {
+        self.transforms
+            .iter()
+            .filter(|(_, t)| t.len() > 1)
+            .map(|(k, t)| {
+                let time_span_seconds = get_time_span_seconds(
+                    k,
+                    &t.first().unwrap().timestamp,
+                    &t.last().unwrap().timestamp,
+                )?;
+                Ok((k.clone(), (t.len() - 1) as f64 / time_span_seconds))
+            })
+            .collect()
+    }
+
+    /// Returns the number of intervals between consecutive samples per bin of the given width.
+    ///
+    /// The key is the bin index, i.e. the interval length divided by `bin`, which surfaces
+    /// irregular sampling such as a sensor temporarily dropping to half its rate. Fails with
+    /// [`Error::InvalidBinWidth`], if `bin` is not positive.
+    pub fn get_interval_histogram(
+        &self,
+        channel_id: &ChannelId,
+        transform_id: &TransformId,
+        bin: Duration,
+    ) -> Result<BTreeMap<i64, usize>, Error> {
+        let bin_nanoseconds = bin
+            .num_nanoseconds()
+            .filter(|b| *b > 0)
+            .ok_or(InvalidBinWidth(bin))?;
+        let transforms = self
+            .transforms
+            .get(&(channel_id.clone(), transform_id.clone()))
+            .ok_or_else(|| InvalidTransformId(channel_id.clone(), transform_id.clone()))?;
+
+        let mut histogram: BTreeMap<i64, usize> = BTreeMap::new();
+        for (previous, next) in get_intervals(transforms) {
+            let interval = next.timestamp - previous.timestamp;
+            let interval_nanoseconds = interval
+                .num_nanoseconds()
+                .ok_or(DurationOverflow(interval))?;
+            *histogram
+                .entry(interval_nanoseconds / bin_nanoseconds)
+                .or_default() += 1;
+        }
+        Ok(histogram)
+    }
+
+    /// Shifts the timestamps of all transforms by a constant offset.
+    ///
+    /// Useful for synchronizing transforms, which were recorded with an offset clock.
+    pub fn time_shift(&mut self, delta: Duration) {
+        self.transforms
+            .values_mut()
+            .flat_map(|t| t.iter_mut())
+            .for_each(|t| t.timestamp += delta);
+    }
+
     pub fn set_interpolation_method(
         &mut self,
         transform_id: TransformId,
@@ -261,31 +532,20 @@ impl ReferenceFrames {
             return Err(InvalidChannelId(channel_id.clone()));
         }
 
-        let all_transforms: Vec<&Transform> = self
+        let transforms = self
             .transforms
             .get(&(channel_id.clone(), transform_id.clone()))
-            .ok_or_else(|| InvalidTransformId(channel_id.clone(), transform_id.clone()))?
-            .iter()
-            .collect();
+            .ok_or_else(|| InvalidTransformId(channel_id.clone(), transform_id.clone()))?;
+        let all_transforms: Vec<&Transform> = transforms.iter().collect();
 
         if timestamp.is_none() {
             return Ok(all_transforms);
         }
         let timestamp = timestamp.unwrap();
 
-        let mut time_based_filtered_transforms: Vec<&Transform> = all_transforms
-            .clone()
-            .windows(2)
-            .filter(|t| {
-                t[0].timestamp.timestamp_nanos() <= timestamp.timestamp_nanos()
-                    && timestamp.timestamp_nanos() < t[1].timestamp.timestamp_nanos()
-            })
-            /*.filter(|t| {
-                t[0].duration
-                    .map_or(false, |d| timestamp <= t[0].timestamp + d)
-                    || timestamp.timestamp_nanos() < t[1].timestamp.timestamp_nanos()
-            })*/
-            .map(|t| t[0])
+        let mut time_based_filtered_transforms: Vec<&Transform> = get_intervals(transforms)
+            .filter(|(start, end)| start.timestamp <= timestamp && timestamp < end.timestamp)
+            .map(|(start, _)| start)
             .collect();
 
         if all_transforms.last().unwrap().timestamp <= timestamp {
@@ -295,6 +555,51 @@ impl ReferenceFrames {
         Ok(time_based_filtered_transforms)
     }
 
+    /// Returns the linear velocity in units per second and the angular velocity as scaled axis in
+    /// radians per second, both expressed in the parent frame, i.e. the `frame_id` of the
+    /// transform id.
+    ///
+    /// The velocities are the finite differences of the two samples enclosing the timestamp.
+    /// Before the first and after the last sample, the first and last interval is used. A single
+    /// sample results in zero velocities. Fails with [`Error::DurationOverflow`], if the interval
+    /// is not representable in nanoseconds, and with [`Error::TransformsNotSorted`], if the
+    /// interval is not positive.
+    pub fn get_velocity(
+        &self,
+        channel_id: &ChannelId,
+        transform_id: &TransformId,
+        timestamp: &DateTime<Utc>,
+    ) -> Result<(Vector3<f64>, Vector3<f64>), Error> {
+        let transforms = self
+            .transforms
+            .get(&(channel_id.clone(), transform_id.clone()))
+            .ok_or_else(|| InvalidTransformId(channel_id.clone(), transform_id.clone()))?;
+        if transforms.len() < 2 {
+            return Ok((Vector3::zeros(), Vector3::zeros()));
+        }
+
+        let (previous, next) =
+            get_interval_containing(transforms, timestamp).unwrap_or_else(|| {
+                let last_interval_start = transforms.len() - 2;
+                let interval_start = if *timestamp < transforms[0].timestamp {
+                    0
+                } else {
+                    last_interval_start
+                };
+                (&transforms[interval_start], &transforms[interval_start + 1])
+            });
+        let seconds = get_time_span_seconds(
+            &(channel_id.clone(), transform_id.clone()),
+            &previous.timestamp,
+            &next.timestamp,
+        )?;
+
+        let linear_velocity = (next.translation - previous.translation) / seconds;
+        let angular_velocity =
+            (next.rotation * previous.rotation.inverse()).scaled_axis() / seconds;
+        Ok((linear_velocity, angular_velocity))
+    }
+
     pub fn get_channel_ids(&self) -> HashSet<ChannelId> {
         self.transforms
             .keys()
@@ -302,6 +607,13 @@ impl ReferenceFrames {
             .collect()
     }
 
+    pub fn get_transform_ids(&self) -> HashSet<TransformId> {
+        self.transforms
+            .keys()
+            .map(|(_, transform_id)| transform_id.clone())
+            .collect()
+    }
+
     pub fn get_frame_ids(&self) -> HashSet<FrameId> {
         self.transforms
             .keys()
@@ -314,6 +626,247 @@ impl ReferenceFrames {
             .collect()
     }
 
+    /// Returns the sorted ids of all frames, whose frame info has neither an EPSG code nor a WKT
+    /// definition as CRS.
+    ///
+    /// Frames without a frame info entry are not considered.
+    pub fn get_frames_without_crs(&self) -> Vec<FrameId> {
+        self.frame_info
+            .iter()
+            .filter(|(_, f)| f.crs_epsg.is_none() && f.crs_wkt.is_none())
+            .map(|(frame_id, _)| frame_id.clone())
+            .sorted()
+            .collect()
+    }
+
+    /// Returns the ids of all transforms, which have the frame as frame or child frame.
+    pub fn get_transform_ids_touching(&self, frame_id: &FrameId) -> HashSet<TransformId> {
+        self.transforms
+            .keys()
+            .map(|(_, transform_id)| transform_id)
+            .filter(|t| &t.frame_id == frame_id || &t.child_frame_id == frame_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns a hash of the frame connectivity, e.g. for use as a cache key.
+    ///
+    /// Only the deduplicated and sorted set of transform ids is hashed, so that the channels, the
+    /// transform samples and the insertion order have no influence. The hash is stable across
+    /// runs, but not guaranteed to be stable across Rust releases.
+    pub fn topology_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.transforms
+            .keys()
+            .map(|(_, transform_id)| transform_id)
+            .sorted()
+            .dedup()
+            .for_each(|t| t.hash(&mut hasher));
+        hasher.finish()
+    }
+
+    /// Returns for each frame the set of frames, which are reachable by following the transforms
+    /// from frame to child frame (transitive closure across all channels).
+    pub fn get_reachability(&self) -> HashMap<FrameId, HashSet<FrameId>> {
+        let child_frame_ids = self.get_child_frame_ids();
+
+        self.get_frame_ids()
+            .into_iter()
+            .map(|frame_id| {
+                let mut reachable_frame_ids: HashSet<FrameId> = HashSet::new();
+                let mut stack: Vec<&FrameId> = vec![&frame_id];
+                while let Some(current_frame_id) = stack.pop() {
+                    for &current_child_frame_id in
+                        child_frame_ids.get(current_frame_id).into_iter().flatten()
+                    {
+                        if reachable_frame_ids.insert(current_child_frame_id.clone()) {
+                            stack.push(current_child_frame_id);
+                        }
+                    }
+                }
+                (frame_id, reachable_frame_ids)
+            })
+            .collect()
+    }
+
+    /// Returns the sorted child frames of each frame across all channels.
+    fn get_child_frame_ids(&self) -> HashMap<&FrameId, Vec<&FrameId>> {
+        let mut child_frame_ids: HashMap<&FrameId, Vec<&FrameId>> = HashMap::new();
+        for (_, transform_id) in self.transforms.keys() {
+            child_frame_ids
+                .entry(&transform_id.frame_id)
+                .or_default()
+                .push(&transform_id.child_frame_id);
+        }
+        child_frame_ids.values_mut().for_each(|c| {
+            c.sort();
+            c.dedup();
+        });
+        child_frame_ids
+    }
+
+    /// Returns the frame closing the first cycle found by a depth-first search, which starts from
+    /// the frames in sorted order.
+    fn find_cycle(&self) -> Option<FrameId> {
+        let child_frame_ids = self.get_child_frame_ids();
+        let mut visiting: HashSet<&FrameId> = HashSet::new();
+        let mut visited: HashSet<&FrameId> = HashSet::new();
+
+        for &start_frame_id in child_frame_ids.keys().sorted() {
+            if visited.contains(start_frame_id) {
+                continue;
+            }
+            visiting.insert(start_frame_id);
+            let mut stack: Vec<(&FrameId, usize)> = vec![(start_frame_id, 0)];
+            while let Some((current_frame_id, next_child_index)) = stack.last_mut() {
+                let current_child_frame_id = child_frame_ids
+                    .get(current_frame_id)
+                    .and_then(|c| c.get(*next_child_index));
+                *next_child_index += 1;
+
+                match current_child_frame_id {
+                    Some(&child_frame_id) if visiting.contains(child_frame_id) => {
+                        return Some(child_frame_id.clone());
+                    }
+                    Some(&child_frame_id) if !visited.contains(child_frame_id) => {
+                        visiting.insert(child_frame_id);
+                        stack.push((child_frame_id, 0));
+                    }
+                    Some(_) => {}
+                    None => {
+                        visiting.remove(*current_frame_id);
+                        visited.insert(*current_frame_id);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns true, if the `to` frame is reachable from the `from` frame by following the
+    /// transforms from frame to child frame.
+    fn is_reachable(&self, from: &FrameId, to: &FrameId) -> bool {
+        let child_frame_ids = self.get_child_frame_ids();
+        let mut visited: HashSet<&FrameId> = HashSet::new();
+        let mut stack: Vec<&FrameId> = vec![from];
+        while let Some(current_frame_id) = stack.pop() {
+            for &current_child_frame_id in
+                child_frame_ids.get(current_frame_id).into_iter().flatten()
+            {
+                if current_child_frame_id == to {
+                    return true;
+                }
+                if visited.insert(current_child_frame_id) {
+                    stack.push(current_child_frame_id);
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns the transform from the `from` frame to the `to` frame resolved at the timestamp
+    /// across all channels.
+    ///
+    /// The frames do not need to be ancestors of each other, but must share a common root, as for
+    /// instance sensor frames below the same `base_link`.
+    pub fn get_relative_transform(
+        &self,
+        from: &FrameId,
+        to: &FrameId,
+        timestamp: &DateTime<Utc>,
+    ) -> Result<Transform, Error> {
+        if from == to {
+            if !self.get_frame_ids().contains(from) {
+                return Err(InvalidFrameId(from.clone()));
+            }
+            return Ok(Transform::identity(*timestamp));
+        }
+
+        let isometry = self
+            .derive_transform_graph(&None, &Some(*timestamp))?
+            .get_isometry(&TransformId::new(from.clone(), to.clone()))?;
+        Ok(Transform::from(*timestamp, isometry))
+    }
+
+    /// Returns the pose of the `to` frame in the `from` frame for visualization.
+    ///
+    /// The relative transform is composed with the display rotation of the `to` frame, if one is
+    /// set in its frame info. The transforms themselves are not altered.
+    pub fn get_display_pose(
+        &self,
+        from: &FrameId,
+        to: &FrameId,
+        timestamp: &DateTime<Utc>,
+    ) -> Result<Transform, Error> {
+        let mut pose = self.get_relative_transform(from, to, timestamp)?;
+        if let Some(display_rotation) = self.frame_info.get(to).and_then(|f| f.display_rotation()) {
+            pose.rotation *= display_rotation;
+        }
+        Ok(pose)
+    }
+
+    /// Returns the frame, whose origin is closest to a point, and the distance.
+    ///
+    /// Only frames reachable from the reference frame are considered.
+    ///
+    /// * `reference_frame_id` - Frame, in which the point is expressed.
+    /// * `point` - Point in coordinates of the reference frame.
+    /// * `timestamp` - Timestamp to choose for interpolating time-dependent transforms.
+    pub fn get_closest_frame_origin(
+        &self,
+        reference_frame_id: &FrameId,
+        point: &Point3<f64>,
+        timestamp: &DateTime<Utc>,
+    ) -> Result<(FrameId, f64), Error> {
+        let reachable_frame_ids = self
+            .get_reachability()
+            .remove(reference_frame_id)
+            .ok_or_else(|| InvalidFrameId(reference_frame_id.clone()))?;
+        let transform_graph = self.derive_transform_graph(&None, &Some(*timestamp))?;
+
+        let mut closest: Option<(FrameId, f64)> = None;
+        for current_frame_id in reachable_frame_ids {
+            let isometry = transform_graph.get_isometry(&TransformId::new(
+                reference_frame_id.clone(),
+                current_frame_id.clone(),
+            ))?;
+            let current_distance = distance(&Point3::from(isometry.translation.vector), point);
+
+            if closest.as_ref().is_none_or(|(_, d)| current_distance < *d) {
+                closest = Some((current_frame_id, current_distance));
+            }
+        }
+
+        closest.ok_or(MissingTransforms())
+    }
+
+    /// Returns for each transform contained in both reference frames the translation distance and
+    /// the rotation angle in radians between the transforms resolved at `timestamp`.
+    ///
+    /// Transforms contained in only one of the reference frames are not part of the result and
+    /// can be determined via [`ReferenceFrames::get_transform_ids`].
+    pub fn difference(
+        &self,
+        other: &ReferenceFrames,
+        timestamp: &DateTime<Utc>,
+    ) -> Result<HashMap<TransformId, (f64, f64)>, Error> {
+        let transform_graph = self.derive_transform_graph(&None, &Some(*timestamp))?;
+        let other_transform_graph = other.derive_transform_graph(&None, &Some(*timestamp))?;
+
+        self.get_transform_ids()
+            .intersection(&other.get_transform_ids())
+            .map(|transform_id| {
+                let isometry = transform_graph.get_isometry(transform_id)?;
+                let other_isometry = other_transform_graph.get_isometry(transform_id)?;
+                let translation_distance =
+                    (isometry.translation.vector - other_isometry.translation.vector).norm();
+                let rotation_angle = isometry.rotation.angle_to(&other_isometry.rotation);
+                Ok((transform_id.clone(), (translation_distance, rotation_angle)))
+            })
+            .collect()
+    }
+
     /*pub fn get_channel_names(&self) -> HashSet<ChannelId> {
         self.transforms
             .keys()
@@ -356,6 +909,9 @@ impl ReferenceFrames {
         if transforms.is_empty() {
             return Err(MissingTransforms());
         }
+        if self.is_reachable(&transform_id.child_frame_id, &transform_id.frame_id) {
+            return Err(CyclicTransformGraph(transform_id.frame_id));
+        }
 
         self.transforms
             .insert((channel_id.clone(), transform_id.clone()), transforms);
@@ -377,6 +933,15 @@ impl ReferenceFrames {
             .and_then(|o| o.interpolation_method)
     }
 
+    pub fn get_rotation_interpolation(
+        &self,
+        transform_id: &TransformId,
+    ) -> Option<RotationInterpolation> {
+        self.transform_info
+            .get(transform_id)
+            .and_then(|o| o.rotation_interpolation)
+    }
+
     /// Derive a concrete transform graph for a specific timestamp and selected channels.
     ///
     /// * `selected_channel_ids` - Selected channels for building the transform graph.
@@ -428,10 +993,14 @@ impl ReferenceFrames {
             let interpolation_method = self
                 .get_interpolation_method(&current_transform_id)
                 .unwrap_or_default();
+            let rotation_interpolation = self
+                .get_rotation_interpolation(&current_transform_id)
+                .unwrap_or_default();
             let interpolated_transform = interpolate_transforms(
                 &current_transforms,
                 selected_timestamp,
                 interpolation_method,
+                rotation_interpolation,
             )?;
             selected_isometries.insert(current_transform_id.clone(), interpolated_transform);
 
@@ -488,3 +1057,783 @@ impl ReferenceFrames {
     //         .unwrap()
     // }
 }
+
+/// Returns the seconds between two samples of the channel and transform, which must be
+/// ascending.
+fn get_time_span_seconds(
+    (channel_id, transform_id): &(ChannelId, TransformId),
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+) -> Result<f64, Error> {
+    let time_span = *end - *start;
+    let nanoseconds = time_span
+        .num_nanoseconds()
+        .ok_or(DurationOverflow(time_span))?;
+    if nanoseconds <= 0 {
+        return Err(TransformsNotSorted {
+            channel_id: channel_id.clone(),
+            transform_id: transform_id.clone(),
+        });
+    }
+    Ok(nanoseconds as f64 / 1e9)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use nalgebra::UnitQuaternion;
+
+    fn transform_at(sec: i64, x: f64) -> Transform {
+        Transform::new(
+            Utc.timestamp_opt(sec, 0).unwrap(),
+            Vector3::new(x, 0.0, 0.0),
+            UnitQuaternion::identity(),
+        )
+    }
+
+    fn reference_frames_from_edges(edges: &[(&str, &str)]) -> ReferenceFrames {
+        let transforms: HashMap<(ChannelId, TransformId), Vec<Transform>> = edges
+            .iter()
+            .map(|(frame_id, child_frame_id)| {
+                (
+                    (
+                        ChannelId::from("default"),
+                        TransformId::new(FrameId::from(*frame_id), FrameId::from(*child_frame_id)),
+                    ),
+                    vec![transform_at(0, 1.0)],
+                )
+            })
+            .collect();
+        ReferenceFrames::new(transforms, HashMap::new(), HashMap::new(), HashMap::new()).unwrap()
+    }
+
+    fn frame_ids(frame_ids: &[&str]) -> HashSet<FrameId> {
+        frame_ids.iter().map(|f| FrameId::from(*f)).collect()
+    }
+
+    #[test]
+    fn test_total_sample_count() {
+        let mut transforms: HashMap<(ChannelId, TransformId), Vec<Transform>> = HashMap::new();
+        transforms.insert(
+            (
+                ChannelId::from("calibration"),
+                TransformId::new(FrameId::from("base_link"), FrameId::from("lidar")),
+            ),
+            vec![transform_at(0, 1.0)],
+        );
+        transforms.insert(
+            (
+                ChannelId::from("slam"),
+                TransformId::new(FrameId::from("map"), FrameId::from("base_link")),
+            ),
+            vec![
+                transform_at(0, 0.0),
+                transform_at(1, 1.0),
+                transform_at(2, 2.0),
+            ],
+        );
+        let reference_frames =
+            ReferenceFrames::new(transforms, HashMap::new(), HashMap::new(), HashMap::new())
+                .unwrap();
+
+        assert_eq!(reference_frames.total_sample_count(), 4);
+    }
+
+    #[test]
+    fn test_time_shift() {
+        let transform_id = TransformId::new(FrameId::from("map"), FrameId::from("base_link"));
+        let mut transforms: HashMap<(ChannelId, TransformId), Vec<Transform>> = HashMap::new();
+        transforms.insert(
+            (ChannelId::from("slam"), transform_id.clone()),
+            vec![transform_at(0, 0.0), transform_at(10, 10.0)],
+        );
+        let mut transform_info: HashMap<TransformId, TransformInfo> = HashMap::new();
+        transform_info.insert(
+            transform_id.clone(),
+            TransformInfo::new(Some(InterpolationMethod::Linear), None),
+        );
+        let reference_frames =
+            ReferenceFrames::new(transforms, HashMap::new(), HashMap::new(), transform_info)
+                .unwrap();
+        let timestamp = Utc.timestamp_opt(3, 0).unwrap();
+        let expected = reference_frames
+            .derive_transform_graph(&None, &Some(timestamp))
+            .unwrap()
+            .get_isometry(&transform_id)
+            .unwrap();
+
+        for delta in [Duration::seconds(5), Duration::seconds(-5)] {
+            let mut shifted_reference_frames = reference_frames.clone();
+            shifted_reference_frames.time_shift(delta);
+
+            let result = shifted_reference_frames
+                .derive_transform_graph(&None, &Some(timestamp + delta))
+                .unwrap()
+                .get_isometry(&transform_id)
+                .unwrap();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_reachability_chain() {
+        let reference_frames = reference_frames_from_edges(&[("a", "b"), ("b", "c"), ("c", "d")]);
+
+        let reachability = reference_frames.get_reachability();
+
+        assert_eq!(reachability.len(), 4);
+        assert_eq!(
+            reachability[&FrameId::from("a")],
+            frame_ids(&["b", "c", "d"])
+        );
+        assert_eq!(reachability[&FrameId::from("b")], frame_ids(&["c", "d"]));
+        assert_eq!(reachability[&FrameId::from("c")], frame_ids(&["d"]));
+        assert_eq!(reachability[&FrameId::from("d")], frame_ids(&[]));
+    }
+
+    #[test]
+    fn test_reachability_diamond() {
+        let reference_frames =
+            reference_frames_from_edges(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d")]);
+
+        let reachability = reference_frames.get_reachability();
+
+        assert_eq!(
+            reachability[&FrameId::from("a")],
+            frame_ids(&["b", "c", "d"])
+        );
+        assert_eq!(reachability[&FrameId::from("b")], frame_ids(&["d"]));
+        assert_eq!(reachability[&FrameId::from("c")], frame_ids(&["d"]));
+        assert_eq!(reachability[&FrameId::from("d")], frame_ids(&[]));
+    }
+
+    #[test]
+    fn test_closest_frame_origin() {
+        let mut transforms: HashMap<(ChannelId, TransformId), Vec<Transform>> = HashMap::new();
+        for (frame_id, child_frame_id, translation) in [
+            ("base_link", "lidar", Vector3::new(1.0, 0.0, 0.0)),
+            ("base_link", "camera", Vector3::new(0.0, 2.0, 0.0)),
+            ("camera", "camera_optical", Vector3::new(0.0, 0.0, 1.0)),
+        ] {
+            transforms.insert(
+                (
+                    ChannelId::from("calibration"),
+                    TransformId::new(FrameId::from(frame_id), FrameId::from(child_frame_id)),
+                ),
+                vec![Transform::new(
+                    Utc.timestamp_opt(0, 0).unwrap(),
+                    translation,
+                    UnitQuaternion::identity(),
+                )],
+            );
+        }
+        let reference_frames =
+            ReferenceFrames::new(transforms, HashMap::new(), HashMap::new(), HashMap::new())
+                .unwrap();
+        let timestamp = Utc.timestamp_opt(0, 0).unwrap();
+
+        let (frame_id, distance) = reference_frames
+            .get_closest_frame_origin(
+                &FrameId::from("base_link"),
+                &Point3::new(0.0, 2.0, 0.6),
+                &timestamp,
+            )
+            .unwrap();
+        assert_eq!(frame_id, FrameId::from("camera_optical"));
+        approx::assert_relative_eq!(distance, 0.4, epsilon = 1e-12);
+
+        let (frame_id, _) = reference_frames
+            .get_closest_frame_origin(
+                &FrameId::from("base_link"),
+                &Point3::new(0.9, 0.1, 0.0),
+                &timestamp,
+            )
+            .unwrap();
+        assert_eq!(frame_id, FrameId::from("lidar"));
+
+        assert!(reference_frames
+            .get_closest_frame_origin(&FrameId::from("unknown"), &Point3::origin(), &timestamp)
+            .is_err());
+    }
+
+    #[test]
+    fn test_sample_rates() {
+        let trajectory_key = (
+            ChannelId::from("slam"),
+            TransformId::new(FrameId::from("map"), FrameId::from("base_link")),
+        );
+        let mut transforms: HashMap<(ChannelId, TransformId), Vec<Transform>> = HashMap::new();
+        transforms.insert(
+            trajectory_key.clone(),
+            (0..50)
+                .map(|i| {
+                    Transform::new(
+                        Utc.timestamp_opt(100, 0).unwrap() + Duration::milliseconds(100 * i),
+                        Vector3::new(i as f64, 0.0, 0.0),
+                        UnitQuaternion::identity(),
+                    )
+                })
+                .collect(),
+        );
+        transforms.insert(
+            (
+                ChannelId::from("calibration"),
+                TransformId::new(FrameId::from("base_link"), FrameId::from("lidar")),
+            ),
+            vec![transform_at(0, 1.0)],
+        );
+        let reference_frames =
+            ReferenceFrames::new(transforms, HashMap::new(), HashMap::new(), HashMap::new())
+                .unwrap();
+
+        let sample_rates = reference_frames.get_sample_rates().unwrap();
+
+        assert_eq!(sample_rates.len(), 1);
+        approx::assert_relative_eq!(sample_rates[&trajectory_key], 10.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_get_sample_rates_invalid_time_span() {
+        let trajectory_key = (
+            ChannelId::from("slam"),
+            TransformId::new(FrameId::from("map"), FrameId::from("base_link")),
+        );
+        let mut reference_frames = ReferenceFrames::default();
+        reference_frames
+            .transforms
+            .insert(trajectory_key.clone(), vec![transform_at(1, 0.0); 2]);
+        let mut overflowing = reference_frames.clone();
+        overflowing.transforms.insert(
+            trajectory_key,
+            vec![
+                Transform::identity(DateTime::<Utc>::MIN_UTC),
+                Transform::identity(DateTime::<Utc>::MAX_UTC),
+            ],
+        );
+
+        assert!(matches!(
+            reference_frames.get_sample_rates(),
+            Err(TransformsNotSorted { .. })
+        ));
+        assert!(matches!(
+            overflowing.get_sample_rates(),
+            Err(DurationOverflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_transform_ids_touching() {
+        let reference_frames = reference_frames_from_edges(&[
+            ("map", "base_link"),
+            ("base_link", "lidar"),
+            ("base_link", "camera"),
+        ]);
+
+        let interior = reference_frames.get_transform_ids_touching(&FrameId::from("base_link"));
+        let leaf = reference_frames.get_transform_ids_touching(&FrameId::from("camera"));
+
+        assert_eq!(
+            interior,
+            HashSet::from([
+                TransformId::new(FrameId::from("map"), FrameId::from("base_link")),
+                TransformId::new(FrameId::from("base_link"), FrameId::from("lidar")),
+                TransformId::new(FrameId::from("base_link"), FrameId::from("camera")),
+            ])
+        );
+        assert_eq!(
+            leaf,
+            HashSet::from([TransformId::new(
+                FrameId::from("base_link"),
+                FrameId::from("camera")
+            )])
+        );
+    }
+
+    #[test]
+    fn test_topology_hash() {
+        let reference_frames =
+            reference_frames_from_edges(&[("world", "map"), ("map", "base"), ("base", "lidar")]);
+        let reordered =
+            reference_frames_from_edges(&[("base", "lidar"), ("world", "map"), ("map", "base")]);
+        let different =
+            reference_frames_from_edges(&[("world", "map"), ("map", "base"), ("map", "lidar")]);
+
+        assert_eq!(reference_frames.topology_hash(), reordered.topology_hash());
+        assert_ne!(reference_frames.topology_hash(), different.topology_hash());
+    }
+
+    #[test]
+    fn test_difference() {
+        let reference_frames =
+            reference_frames_from_edges(&[("world", "map"), ("map", "base"), ("base", "lidar")]);
+        let mut other = reference_frames_from_edges(&[("world", "map"), ("map", "base")]);
+        let rotated_transform = Transform::new(
+            Utc.timestamp_opt(0, 0).unwrap(),
+            Vector3::new(1.0, 0.0, 0.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, 0.1),
+        );
+        other
+            .add_transform(
+                ChannelId::from("calibration"),
+                TransformId::new(FrameId::from("map"), FrameId::from("base")),
+                vec![rotated_transform],
+                Some(ChannelInfo::new(Some(1))),
+                None,
+            )
+            .unwrap();
+
+        let difference = reference_frames
+            .difference(&other, &Utc.timestamp_opt(0, 0).unwrap())
+            .unwrap();
+
+        let world_map = TransformId::new(FrameId::from("world"), FrameId::from("map"));
+        let map_base = TransformId::new(FrameId::from("map"), FrameId::from("base"));
+        assert_eq!(difference.len(), 2);
+        assert_eq!(difference[&world_map], (0.0, 0.0));
+        assert!(difference[&map_base].0.abs() < 1e-12);
+        assert!((difference[&map_base].1 - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_get_timed_subset_strict() {
+        let transforms = HashMap::from([
+            (
+                (
+                    ChannelId::from("slam"),
+                    TransformId::new(FrameId::from("world"), FrameId::from("base")),
+                ),
+                vec![transform_at(0, 0.0), transform_at(10, 1.0)],
+            ),
+            (
+                (
+                    ChannelId::from("gnss"),
+                    TransformId::new(FrameId::from("world"), FrameId::from("antenna")),
+                ),
+                vec![transform_at(0, 0.0), transform_at(4, 1.0)],
+            ),
+            (
+                (
+                    ChannelId::from("calibration"),
+                    TransformId::new(FrameId::from("base"), FrameId::from("lidar")),
+                ),
+                vec![transform_at(0, 1.0)],
+            ),
+        ]);
+        let reference_frames =
+            ReferenceFrames::new(transforms, HashMap::new(), HashMap::new(), HashMap::new())
+                .unwrap();
+
+        let covered = reference_frames.get_timed_subset_strict(&Utc.timestamp_opt(3, 0).unwrap());
+        let uncovered = reference_frames.get_timed_subset_strict(&Utc.timestamp_opt(5, 0).unwrap());
+
+        assert!(covered.is_ok());
+        match uncovered {
+            Err(TimestampOutsideSampleRange(ids)) => assert_eq!(
+                ids,
+                vec![(
+                    ChannelId::from("gnss"),
+                    TransformId::new(FrameId::from("world"), FrameId::from("antenna"))
+                )]
+            ),
+            _ => panic!("expected an error for the gnss transform"),
+        }
+    }
+
+    #[test]
+    fn test_new_with_cycle() {
+        let result = ReferenceFrames::new(
+            reference_frames_from_edges(&[("a", "b"), ("b", "c")])
+                .transforms
+                .into_iter()
+                .chain([(
+                    (
+                        ChannelId::from("default"),
+                        TransformId::new(FrameId::from("c"), FrameId::from("a")),
+                    ),
+                    vec![transform_at(0, 1.0)],
+                )])
+                .collect(),
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+        );
+
+        assert!(matches!(result, Err(CyclicTransformGraph(f)) if f == FrameId::from("a")));
+    }
+
+    #[test]
+    fn test_new_with_shared_descendant() {
+        let reference_frames =
+            reference_frames_from_edges(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d")]);
+
+        assert_eq!(reference_frames.find_cycle(), None);
+        assert!(reference_frames.is_reachable(&"a".into(), &"d".into()));
+        assert!(!reference_frames.is_reachable(&"d".into(), &"a".into()));
+    }
+
+    #[test]
+    fn test_add_transform_with_cycle() {
+        let mut reference_frames = reference_frames_from_edges(&[("a", "b"), ("b", "c")]);
+
+        let result = reference_frames.add_transform(
+            ChannelId::from("other"),
+            TransformId::new(FrameId::from("c"), FrameId::from("a")),
+            vec![transform_at(0, 1.0)],
+            None,
+            None,
+        );
+
+        assert!(matches!(result, Err(CyclicTransformGraph(_))));
+        assert_eq!(reference_frames.transforms().len(), 2);
+    }
+
+    #[test]
+    fn test_get_velocity() {
+        let channel_id = ChannelId::from("slam");
+        let transform_id = TransformId::new(FrameId::from("world"), FrameId::from("base"));
+        let transforms: Vec<Transform> = (0..4)
+            .map(|i| {
+                Transform::new(
+                    Utc.timestamp_opt(2 * i, 0).unwrap(),
+                    Vector3::new(3.0 * i as f64, -(i as f64), 0.0),
+                    UnitQuaternion::from_euler_angles(0.0, 0.0, 0.2 * i as f64),
+                )
+            })
+            .collect();
+        let reference_frames = ReferenceFrames::new(
+            HashMap::from([((channel_id.clone(), transform_id.clone()), transforms)]),
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+        )
+        .unwrap();
+
+        for sec in [-1, 0, 3, 6, 10] {
+            let (linear_velocity, angular_velocity) = reference_frames
+                .get_velocity(
+                    &channel_id,
+                    &transform_id,
+                    &Utc.timestamp_opt(sec, 0).unwrap(),
+                )
+                .unwrap();
+
+            assert!((linear_velocity - Vector3::new(1.5, -0.5, 0.0)).norm() < 1e-12);
+            assert!((angular_velocity - Vector3::new(0.0, 0.0, 0.1)).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_get_velocity_rotating() {
+        let channel_id = ChannelId::from("slam");
+        let transform_id = TransformId::new(FrameId::from("world"), FrameId::from("base"));
+        // constant rotation about the z axis of the parent frame, starting from a child frame,
+        // whose z axis points along the parent's y axis
+        let initial_rotation =
+            UnitQuaternion::from_euler_angles(std::f64::consts::FRAC_PI_2, 0.0, 0.0);
+        let transforms: Vec<Transform> = (0..4)
+            .map(|i| {
+                Transform::new(
+                    Utc.timestamp_opt(i, 0).unwrap(),
+                    Vector3::zeros(),
+                    UnitQuaternion::from_euler_angles(0.0, 0.0, 0.3 * i as f64) * initial_rotation,
+                )
+            })
+            .collect();
+        let mut reference_frames = ReferenceFrames::new(
+            HashMap::from([((channel_id.clone(), transform_id.clone()), transforms)]),
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+        )
+        .unwrap();
+
+        for sec in [0, 1, 2, 5] {
+            let (linear_velocity, angular_velocity) = reference_frames
+                .get_velocity(
+                    &channel_id,
+                    &transform_id,
+                    &Utc.timestamp_opt(sec, 0).unwrap(),
+                )
+                .unwrap();
+
+            assert!(linear_velocity.norm() < 1e-12);
+            assert!((angular_velocity - Vector3::new(0.0, 0.0, 0.3)).norm() < 1e-12);
+        }
+
+        reference_frames.transforms.insert(
+            (channel_id.clone(), transform_id.clone()),
+            vec![transform_at(1, 0.0); 2],
+        );
+        assert!(matches!(
+            reference_frames.get_velocity(
+                &channel_id,
+                &transform_id,
+                &Utc.timestamp_opt(1, 0).unwrap()
+            ),
+            Err(TransformsNotSorted { .. })
+        ));
+    }
+
+    #[test]
+    fn test_get_relative_transform() {
+        let base_link = TransformId::new(FrameId::from("world"), FrameId::from("base_link"));
+        let lidar_left = TransformId::new(FrameId::from("base_link"), FrameId::from("lidar_left"));
+        let lidar_right =
+            TransformId::new(FrameId::from("base_link"), FrameId::from("lidar_right"));
+        let transform_at = |x: f64, y: f64, yaw: f64| {
+            vec![Transform::new(
+                Utc.timestamp_opt(0, 0).unwrap(),
+                Vector3::new(x, y, 0.0),
+                UnitQuaternion::from_euler_angles(0.0, 0.0, yaw),
+            )]
+        };
+        let channel_id = ChannelId::from("calibration");
+        let reference_frames = ReferenceFrames::new(
+            HashMap::from([
+                ((channel_id.clone(), base_link), transform_at(5.0, 5.0, 1.0)),
+                (
+                    (channel_id.clone(), lidar_left),
+                    transform_at(1.0, 0.5, std::f64::consts::FRAC_PI_2),
+                ),
+                ((channel_id, lidar_right), transform_at(1.0, -0.5, 0.0)),
+            ]),
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+        )
+        .unwrap();
+        let timestamp = Utc.timestamp_opt(0, 0).unwrap();
+
+        let left_to_right = reference_frames
+            .get_relative_transform(
+                &FrameId::from("lidar_left"),
+                &FrameId::from("lidar_right"),
+                &timestamp,
+            )
+            .unwrap();
+        let right_to_left = reference_frames
+            .get_relative_transform(
+                &FrameId::from("lidar_right"),
+                &FrameId::from("lidar_left"),
+                &timestamp,
+            )
+            .unwrap();
+
+        // the right lidar is one unit along the negative y-axis of base_link, which is the
+        // negative x-axis of the left lidar
+        assert!((left_to_right.translation - Vector3::new(-1.0, 0.0, 0.0)).norm() < 1e-12);
+        assert!(
+            (left_to_right
+                .rotation
+                .angle_to(&UnitQuaternion::from_euler_angles(
+                    0.0,
+                    0.0,
+                    -std::f64::consts::FRAC_PI_2
+                )))
+                < 1e-12
+        );
+        assert!(
+            (left_to_right.isometry() * right_to_left.isometry())
+                .translation
+                .vector
+                .norm()
+                < 1e-12
+        );
+    }
+
+    #[test]
+    fn test_get_display_pose() {
+        let mut reference_frames = reference_frames_from_edges(&[("base_link", "camera")]);
+        let display_rotation = UnitQuaternion::from_euler_angles(
+            -std::f64::consts::FRAC_PI_2,
+            0.0,
+            -std::f64::consts::FRAC_PI_2,
+        );
+        reference_frames.frame_info.insert(
+            FrameId::from("camera"),
+            FrameInfo::new(None).with_display_rotation(display_rotation),
+        );
+        let timestamp = Utc.timestamp_opt(0, 0).unwrap();
+
+        let pose = reference_frames
+            .get_relative_transform(&"base_link".into(), &"camera".into(), &timestamp)
+            .unwrap();
+        let display_pose = reference_frames
+            .get_display_pose(&"base_link".into(), &"camera".into(), &timestamp)
+            .unwrap();
+
+        assert_eq!(display_pose.translation, pose.translation);
+        assert!(
+            display_pose
+                .rotation
+                .angle_to(&(pose.rotation * display_rotation))
+                < 1e-12
+        );
+        assert!(
+            (display_pose.rotation * Vector3::z() - Vector3::x()).norm() < 1e-12,
+            "the camera's z-axis should point forward"
+        );
+    }
+
+    #[test]
+    fn test_retain_frames() {
+        let mut reference_frames = reference_frames_from_edges(&[
+            ("world", "base_link"),
+            ("base_link", "lidar"),
+            ("base_link", "camera"),
+        ]);
+        reference_frames.transforms.insert(
+            (
+                ChannelId::from("default"),
+                TransformId::new("base_link".into(), "camera".into()),
+            ),
+            vec![Transform::new(
+                Utc.timestamp_opt(0, 0).unwrap(),
+                Vector3::new(0.0, 2.0, 1.0),
+                UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5),
+            )],
+        );
+        let timestamp = Utc.timestamp_opt(0, 0).unwrap();
+
+        let result = reference_frames
+            .retain_frames(&frame_ids(&["world", "lidar", "camera"]), &None)
+            .unwrap();
+
+        assert_eq!(
+            result.get_frame_ids(),
+            frame_ids(&["world", "lidar", "camera"])
+        );
+        assert_eq!(
+            result.get_transform_ids(),
+            HashSet::from([
+                TransformId::new("world".into(), "lidar".into()),
+                TransformId::new("world".into(), "camera".into()),
+            ])
+        );
+        for leaf_frame_id in ["lidar", "camera"] {
+            let expected = reference_frames
+                .get_relative_transform(&"world".into(), &leaf_frame_id.into(), &timestamp)
+                .unwrap();
+            let pose = result
+                .get_relative_transform(&"world".into(), &leaf_frame_id.into(), &timestamp)
+                .unwrap();
+            assert!((pose.translation - expected.translation).norm() < 1e-12);
+            assert!(pose.rotation.angle_to(&expected.rotation) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_get_timed_subset_lenient() {
+        let gnss_id = (
+            ChannelId::from("gnss"),
+            TransformId::new(FrameId::from("world"), FrameId::from("antenna")),
+        );
+        let transforms = HashMap::from([
+            (
+                (
+                    ChannelId::from("slam"),
+                    TransformId::new(FrameId::from("world"), FrameId::from("base")),
+                ),
+                vec![transform_at(0, 0.0), transform_at(10, 1.0)],
+            ),
+            (
+                gnss_id.clone(),
+                vec![transform_at(0, 0.0), transform_at(4, 1.0)],
+            ),
+            (
+                (
+                    ChannelId::from("calibration"),
+                    TransformId::new(FrameId::from("base"), FrameId::from("lidar")),
+                ),
+                vec![transform_at(0, 1.0)],
+            ),
+        ]);
+        let reference_frames =
+            ReferenceFrames::new(transforms, HashMap::new(), HashMap::new(), HashMap::new())
+                .unwrap();
+
+        let (subset, skipped_ids) = reference_frames
+            .get_timed_subset_lenient(&Utc.timestamp_opt(5, 0).unwrap())
+            .unwrap();
+
+        assert_eq!(skipped_ids, vec![gnss_id]);
+        assert_eq!(subset.total_sample_count(), 2);
+        assert_eq!(
+            subset.get_frame_ids(),
+            frame_ids(&["world", "base", "lidar"])
+        );
+    }
+
+    #[test]
+    fn test_get_frames_without_crs() {
+        let mut reference_frames = reference_frames_from_edges(&[
+            ("map", "base_link"),
+            ("base_link", "lidar"),
+            ("map", "site"),
+        ]);
+        reference_frames.frame_info = HashMap::from([
+            (FrameId::from("map"), FrameInfo::new(Some(25832))),
+            (
+                FrameId::from("site"),
+                FrameInfo::new(None).with_crs_wkt(r#"ENGCRS["site grid"]"#.to_string()),
+            ),
+            (FrameId::from("lidar"), FrameInfo::new(None)),
+            (FrameId::from("base_link"), FrameInfo::default()),
+        ]);
+
+        let result = reference_frames.get_frames_without_crs();
+
+        assert_eq!(
+            result,
+            vec![FrameId::from("base_link"), FrameId::from("lidar")]
+        );
+    }
+
+    #[test]
+    fn test_get_interval_histogram() {
+        let channel_id = ChannelId::from("gnss");
+        let transform_id = TransformId::new(FrameId::from("world"), FrameId::from("antenna"));
+        let transforms: Vec<Transform> = [0, 100, 200, 300, 500, 700, 800]
+            .into_iter()
+            .map(|millisecond| {
+                Transform::new(
+                    Utc.timestamp_opt(0, millisecond * 1_000_000).unwrap(),
+                    Vector3::zeros(),
+                    UnitQuaternion::identity(),
+                )
+            })
+            .collect();
+        let reference_frames = ReferenceFrames::new(
+            HashMap::from([((channel_id.clone(), transform_id.clone()), transforms)]),
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+        )
+        .unwrap();
+
+        let histogram = reference_frames
+            .get_interval_histogram(&channel_id, &transform_id, Duration::milliseconds(50))
+            .unwrap();
+
+        assert_eq!(histogram, BTreeMap::from([(2, 4), (4, 2)]));
+    }
+
+    #[test]
+    fn test_get_interval_histogram_invalid_bin() {
+        let reference_frames = reference_frames_from_edges(&[("world", "antenna")]);
+        let channel_id = ChannelId::from("default");
+        let transform_id = TransformId::new(FrameId::from("world"), FrameId::from("antenna"));
+
+        let zero =
+            reference_frames.get_interval_histogram(&channel_id, &transform_id, Duration::zero());
+        let negative = reference_frames.get_interval_histogram(
+            &channel_id,
+            &transform_id,
+            Duration::milliseconds(-10),
+        );
+
+        assert!(matches!(zero, Err(InvalidBinWidth(_))));
+        assert!(matches!(negative, Err(InvalidBinWidth(_))));
+    }
+}
@@ -1,9 +1,25 @@
-use crate::FrameId;
-use chrono::{DateTime, Utc};
-use nalgebra::{Isometry3, Point3, Rotation3, Translation3, UnitQuaternion, Vector3};
+use crate::Error::{InvalidCompactTransformString, InvalidQuaternion};
+use crate::{Error, FrameId};
+use chrono::{DateTime, TimeZone, Timelike, Utc};
+use nalgebra::{Isometry3, Point3, Quaternion, Rotation3, Translation3, UnitQuaternion, Vector3};
+use rayon::prelude::*;
+use serde::de::Error as _;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
+use std::ops::Mul;
+
+/// Number of points from which on batch point transformations run in parallel.
+const PARALLEL_POINT_THRESHOLD: usize = 10_000;
+
+/// Separator between the frame id and the child frame id in the string form of a [`TransformId`].
+const TRANSFORM_ID_SEPARATOR: &str = "->";
 
 /// Dedicated type for an identifier of a transform.
+///
+/// Serialized as a string of the form `"frame_id->child_frame_id"`, or as an object with the
+/// fields `frame_id` and `child_frame_id` if a frame id contains the separator `->` itself. For
+/// deserializing, both forms are accepted.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct TransformId {
     pub frame_id: FrameId,
@@ -21,6 +37,14 @@ impl TransformId {
             child_frame_id,
         }
     }
+
+    /// Returns the id of the inverse transform with frame and child frame swapped.
+    pub fn reversed(&self) -> Self {
+        Self {
+            frame_id: self.child_frame_id.clone(),
+            child_frame_id: self.frame_id.clone(),
+        }
+    }
 }
 
 impl fmt::Display for TransformId {
@@ -33,6 +57,65 @@ impl fmt::Display for TransformId {
     }
 }
 
+impl Serialize for TransformId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let contains_separator = [&self.frame_id, &self.child_frame_id]
+            .iter()
+            .any(|f| f.to_string().contains(TRANSFORM_ID_SEPARATOR));
+        if contains_separator {
+            let mut state = serializer.serialize_struct("TransformId", 2)?;
+            state.serialize_field("frame_id", &self.frame_id)?;
+            state.serialize_field("child_frame_id", &self.child_frame_id)?;
+            return state.end();
+        }
+
+        serializer.serialize_str(&format!(
+            "{}{}{}",
+            self.frame_id, TRANSFORM_ID_SEPARATOR, self.child_frame_id
+        ))
+    }
+}
+
+impl<'de> Deserialize<'de> for TransformId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum TransformIdRepr {
+            String(String),
+            Object {
+                frame_id: FrameId,
+                child_frame_id: FrameId,
+            },
+        }
+
+        let (frame_id, child_frame_id) = match TransformIdRepr::deserialize(deserializer)? {
+            TransformIdRepr::String(s) => {
+                let (frame_id, child_frame_id) =
+                    s.split_once(TRANSFORM_ID_SEPARATOR).ok_or_else(|| {
+                        D::Error::custom(format!(
+                            "transform id `{s}` must be of the form `frame_id{TRANSFORM_ID_SEPARATOR}child_frame_id`"
+                        ))
+                    })?;
+                (FrameId::from(frame_id), FrameId::from(child_frame_id))
+            }
+            TransformIdRepr::Object {
+                frame_id,
+                child_frame_id,
+            } => (frame_id, child_frame_id),
+        };
+        if frame_id == child_frame_id {
+            return Err(D::Error::custom(
+                "frame_id must be different from child_frame_id",
+            ));
+        }
+
+        Ok(Self {
+            frame_id,
+            child_frame_id,
+        })
+    }
+}
+
 /// A time-dependent rigid transformation in 3D.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Transform {
@@ -42,6 +125,10 @@ pub struct Transform {
     pub rotation: UnitQuaternion<f64>,
 }
 
+/// Default distance of the pitch to ±π/2 in radians, below which
+/// [`Transform::euler_angles_safe`] flags the angles as unreliable.
+pub const GIMBAL_LOCK_EPSILON: f64 = 1e-3;
+
 impl Transform {
     pub fn new(
         timestamp: DateTime<Utc>,
@@ -72,6 +159,33 @@ impl Transform {
         }
     }
 
+    /// Creates a transform without translation and rotation.
+    pub fn identity(timestamp: DateTime<Utc>) -> Self {
+        Self::new(timestamp, Vector3::zeros(), UnitQuaternion::identity())
+    }
+
+    /// Returns true, if the translation norm and the rotation angle in radians are both within
+    /// `eps`.
+    pub fn is_identity(&self, eps: f64) -> bool {
+        self.translation.norm() <= eps && self.rotation.angle() <= eps
+    }
+
+    /// Returns true, if each translation component differs by at most `epsilon` and the angle
+    /// between the rotations in radians is at most `epsilon`.
+    ///
+    /// The timestamps are ignored, so that poses from interpolation or resampling can be compared.
+    /// Quaternions of opposite sign are considered equal, as they describe the same rotation.
+    pub fn approx_eq(&self, other: &Transform, epsilon: f64) -> bool {
+        // atan2 instead of acos of the dot product, which is imprecise for small angles
+        let difference = self.rotation.inverse() * other.rotation;
+        let angle = 2.0 * difference.imag().norm().atan2(difference.w.abs());
+
+        (self.translation - other.translation)
+            .iter()
+            .all(|d| d.abs() <= epsilon)
+            && angle <= epsilon
+    }
+
     pub fn translation(&self) -> Translation3<f64> {
         Translation3::from(self.translation)
     }
@@ -85,9 +199,669 @@ impl Transform {
         Isometry3::from_parts(translation, self.rotation)
     }
 
+    /// Returns the inverse transform with the same timestamp.
+    pub fn inverse(&self) -> Self {
+        let rotation = self.rotation.inverse();
+        let translation = -(rotation * self.translation);
+        Self::new(self.timestamp, translation, rotation)
+    }
+
+    /// Returns the point transformed by the rotation followed by the translation.
     pub fn transform_point(&self, pt: &Point3<f64>) -> Point3<f64> {
-        let rotated_point = self.rotation().transform_point(pt);
-        let _translated_point = self.translation().transform_point(pt);
-        rotated_point
+        self.isometry().transform_point(pt)
+    }
+
+    /// Returns the transformed points, whereby large batches are processed in parallel.
+    pub fn transform_points(&self, points: &[Point3<f64>]) -> Vec<Point3<f64>> {
+        let isometry = self.isometry();
+        if points.len() < PARALLEL_POINT_THRESHOLD {
+            points.iter().map(|p| isometry.transform_point(p)).collect()
+        } else {
+            points
+                .par_iter()
+                .map(|p| isometry.transform_point(p))
+                .collect()
+        }
+    }
+
+    /// Transforms the points in place, whereby large batches are processed in parallel.
+    pub fn transform_points_mut(&self, points: &mut [Point3<f64>]) {
+        let isometry = self.isometry();
+        if points.len() < PARALLEL_POINT_THRESHOLD {
+            points
+                .iter_mut()
+                .for_each(|p| *p = isometry.transform_point(p));
+        } else {
+            points
+                .par_iter_mut()
+                .for_each(|p| *p = isometry.transform_point(p));
+        }
+    }
+
+    /// Returns the roll, pitch and yaw angles of the rotation together with a flag that is set if
+    /// the pitch is within [`GIMBAL_LOCK_EPSILON`] of ±π/2.
+    ///
+    /// Close to gimbal lock, roll and yaw are no longer independent, so that the returned values
+    /// are numerically unreliable.
+    pub fn euler_angles_safe(&self) -> (f64, f64, f64, bool) {
+        self.euler_angles_safe_with_epsilon(GIMBAL_LOCK_EPSILON)
+    }
+
+    /// Same as [`Transform::euler_angles_safe`], but with a custom `epsilon` in radians.
+    pub fn euler_angles_safe_with_epsilon(&self, epsilon: f64) -> (f64, f64, f64, bool) {
+        let (roll, pitch, yaw) = self.rotation.euler_angles();
+        let near_gimbal_lock = (pitch.abs() - std::f64::consts::FRAC_PI_2).abs() <= epsilon;
+        (roll, pitch, yaw, near_gimbal_lock)
+    }
+
+    /// Decomposes the rotation into a swing and a twist about the `axis`, so that
+    /// `swing * twist` equals the rotation.
+    ///
+    /// The twist is the rotation about the axis, while the swing rotates the axis. If the rotation
+    /// turns the axis by 180°, the twist is not defined and the identity is returned.
+    pub fn swing_twist(&self, axis: &Vector3<f64>) -> (UnitQuaternion<f64>, UnitQuaternion<f64>) {
+        let axis = axis.normalize();
+        let projection = axis * self.rotation.imag().dot(&axis);
+        let twist = Quaternion::from_parts(self.rotation.w, projection);
+
+        let twist = if twist.norm() < f64::EPSILON {
+            UnitQuaternion::identity()
+        } else {
+            UnitQuaternion::from_quaternion(twist)
+        };
+        let swing = self.rotation * twist.inverse();
+        (swing, twist)
+    }
+
+    /// Creates a transform from roll, pitch and yaw angles in degrees.
+    pub fn from_euler_deg(
+        timestamp: DateTime<Utc>,
+        translation: Vector3<f64>,
+        roll: f64,
+        pitch: f64,
+        yaw: f64,
+    ) -> Self {
+        let rotation = UnitQuaternion::from_euler_angles(
+            roll.to_radians(),
+            pitch.to_radians(),
+            yaw.to_radians(),
+        );
+        Self::new(timestamp, translation, rotation)
+    }
+
+    /// Returns the roll, pitch and yaw angles of the rotation in radians.
+    ///
+    /// See [`Transform::euler_angles_safe`] for detecting gimbal lock.
+    pub fn euler_angles_rad(&self) -> (f64, f64, f64) {
+        self.rotation.euler_angles()
+    }
+
+    /// Returns the roll, pitch and yaw angles of the rotation in degrees.
+    pub fn euler_angles_deg(&self) -> (f64, f64, f64) {
+        let (roll, pitch, yaw) = self.euler_angles_rad();
+        (roll.to_degrees(), pitch.to_degrees(), yaw.to_degrees())
+    }
+
+    /// Returns the unit rotation axis and the angle in radians, or `None` for the identity
+    /// rotation.
+    pub fn axis_angle(&self) -> Option<(Vector3<f64>, f64)> {
+        self.rotation
+            .axis_angle()
+            .map(|(axis, angle)| (axis.into_inner(), angle))
+    }
+
+    /// Creates a transform from the fields of a ROS `geometry_msgs/Pose`.
+    ///
+    /// * `position` - Position as `[x, y, z]`.
+    /// * `orientation` - Orientation quaternion as `[x, y, z, w]`, which is the field order of
+    ///   `geometry_msgs/Quaternion`. Must be normalized up to a tolerance of `1e-6`.
+    pub fn from_ros_pose(
+        timestamp: DateTime<Utc>,
+        position: [f64; 3],
+        orientation: [f64; 4],
+    ) -> Result<Self, Error> {
+        let [x, y, z, w] = orientation;
+        let quaternion = Quaternion::new(w, x, y, z);
+        if !orientation.iter().all(|c| c.is_finite()) || (quaternion.norm() - 1.0).abs() > 1e-6 {
+            return Err(InvalidQuaternion(orientation));
+        }
+
+        Ok(Self::new(
+            timestamp,
+            Vector3::from(position),
+            UnitQuaternion::from_quaternion(quaternion),
+        ))
+    }
+
+    /// Returns the position `[x, y, z]` and orientation `[x, y, z, w]` in the field order of a ROS
+    /// `geometry_msgs/Pose`.
+    pub fn to_ros_pose(&self) -> ([f64; 3], [f64; 4]) {
+        (
+            self.translation.into(),
+            [
+                self.rotation.i,
+                self.rotation.j,
+                self.rotation.k,
+                self.rotation.w,
+            ],
+        )
+    }
+
+    /// Returns a lossless single-line representation, e.g. for logging.
+    ///
+    /// The timestamp is written as `sec.nanosec`, followed by the translation `x y z` and the
+    /// rotation `x y z w`, each as the hexadecimal bit pattern of the `f64`, so that
+    /// [`Transform::from_compact_string`] restores the transform bit for bit.
+    pub fn to_compact_string(&self) -> String {
+        let components = [
+            self.translation.x,
+            self.translation.y,
+            self.translation.z,
+            self.rotation.i,
+            self.rotation.j,
+            self.rotation.k,
+            self.rotation.w,
+        ]
+        .map(|c| format!("{:016x}", c.to_bits()));
+
+        format!(
+            "{}.{:09} {}",
+            self.timestamp.timestamp(),
+            self.timestamp.nanosecond(),
+            components.join(" ")
+        )
+    }
+
+    /// Parses the representation created by [`Transform::to_compact_string`].
+    ///
+    /// The nanoseconds must have exactly nine digits and the rotation must be normalized up to a
+    /// tolerance of `1e-6`.
+    pub fn from_compact_string(value: &str) -> Result<Self, Error> {
+        let invalid = || InvalidCompactTransformString(value.to_string());
+
+        let mut parts = value.split_whitespace();
+        let (sec, nanosec) = parts
+            .next()
+            .and_then(|t| t.split_once('.'))
+            .ok_or_else(invalid)?;
+        if nanosec.len() != 9 || !nanosec.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let timestamp = Utc
+            .timestamp_opt(
+                sec.parse().map_err(|_| invalid())?,
+                nanosec.parse().map_err(|_| invalid())?,
+            )
+            .single()
+            .ok_or_else(invalid)?;
+
+        let components: Vec<f64> = parts
+            .map(|p| u64::from_str_radix(p, 16).map(f64::from_bits))
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid())?;
+        let [x, y, z, qx, qy, qz, qw] = components[..] else {
+            return Err(invalid());
+        };
+        let quaternion = Quaternion::new(qw, qx, qy, qz);
+        if !quaternion.coords.iter().all(|c| c.is_finite())
+            || (quaternion.norm() - 1.0).abs() > 1e-6
+        {
+            return Err(InvalidQuaternion([qx, qy, qz, qw]));
+        }
+
+        // not renormalized, so that the rotation is restored bit for bit
+        Ok(Self::new(
+            timestamp,
+            Vector3::new(x, y, z),
+            UnitQuaternion::new_unchecked(quaternion),
+        ))
+    }
+}
+
+/// Composes two transforms as their isometries, whereby the timestamp of the left-hand side is
+/// kept.
+impl Mul<&Transform> for &Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: &Transform) -> Transform {
+        Transform::new(
+            self.timestamp,
+            self.translation + self.rotation * rhs.translation,
+            self.rotation * rhs.rotation,
+        )
+    }
+}
+
+impl Mul<Transform> for Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: Transform) -> Transform {
+        &self * &rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_string_round_trip() {
+        let transform = Transform::new(
+            Utc.timestamp_opt(-1_234_567, 999_999_999).unwrap(),
+            Vector3::new(f64::MIN_POSITIVE / 3.0, 6.378e12, -0.1),
+            UnitQuaternion::from_euler_angles(0.1, -1e-300, 2.9),
+        );
+
+        let compact_string = transform.to_compact_string();
+        let result = Transform::from_compact_string(&compact_string).unwrap();
+
+        assert!(transform.translation.x.is_subnormal());
+        assert_eq!(result.timestamp, transform.timestamp);
+        for (a, b) in result
+            .translation
+            .iter()
+            .chain(result.rotation.coords.iter())
+            .zip(
+                transform
+                    .translation
+                    .iter()
+                    .chain(transform.rotation.coords.iter()),
+            )
+        {
+            assert_eq!(a.to_bits(), b.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_compact_string_invalid() {
+        assert!(Transform::from_compact_string("").is_err());
+        assert!(Transform::from_compact_string("1.0 3ff0000000000000").is_err());
+        assert!(Transform::from_compact_string("x.0 0 0 0 0 0 0 0").is_err());
+    }
+
+    #[test]
+    fn test_compact_string_invalid_nanosec() {
+        let identity = "0000000000000000 0000000000000000 0000000000000000 \
+            0000000000000000 0000000000000000 0000000000000000 3ff0000000000000";
+
+        assert!(Transform::from_compact_string(&format!("1.000000000 {identity}")).is_ok());
+        assert!(matches!(
+            Transform::from_compact_string(&format!("1.5 {identity}")),
+            Err(InvalidCompactTransformString(_))
+        ));
+        assert!(matches!(
+            Transform::from_compact_string(&format!("1.+00000005 {identity}")),
+            Err(InvalidCompactTransformString(_))
+        ));
+    }
+
+    #[test]
+    fn test_compact_string_unnormalized_rotation() {
+        // rotation (0, 0, 0, 2)
+        let result = Transform::from_compact_string(
+            "1.000000000 0000000000000000 0000000000000000 0000000000000000 \
+            0000000000000000 0000000000000000 0000000000000000 4000000000000000",
+        );
+
+        assert!(matches!(
+            result,
+            Err(InvalidQuaternion([0.0, 0.0, 0.0, 2.0]))
+        ));
+    }
+
+    #[test]
+    fn test_ros_pose_round_trip() {
+        let timestamp = Utc.timestamp_opt(1, 0).unwrap();
+        let position = [1.0, -2.0, 3.5];
+        // 90° about z
+        let orientation = [
+            0.0,
+            0.0,
+            std::f64::consts::FRAC_1_SQRT_2,
+            std::f64::consts::FRAC_1_SQRT_2,
+        ];
+
+        let transform = Transform::from_ros_pose(timestamp, position, orientation).unwrap();
+        let (result_position, result_orientation) = transform.to_ros_pose();
+
+        assert_eq!(transform.rotation.angle(), std::f64::consts::FRAC_PI_2);
+        assert_eq!(transform.rotation.axis().unwrap(), Vector3::z_axis());
+        assert_eq!(result_position, position);
+        assert_eq!(result_orientation, orientation);
+    }
+
+    #[test]
+    fn test_ros_pose_invalid_orientation() {
+        let timestamp = Utc.timestamp_opt(1, 0).unwrap();
+
+        assert!(Transform::from_ros_pose(timestamp, [0.0; 3], [0.0, 0.0, 0.0, 2.0]).is_err());
+        assert!(Transform::from_ros_pose(timestamp, [0.0; 3], [f64::NAN, 0.0, 0.0, 1.0]).is_err());
+    }
+
+    #[test]
+    fn test_transform_id_serde_round_trip() {
+        let transform_id = TransformId::new(FrameId::from("map"), FrameId::from("base_link"));
+
+        let serialized = serde_json::to_string(&transform_id).unwrap();
+        let deserialized: TransformId = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(serialized, "\"map->base_link\"");
+        assert_eq!(deserialized, transform_id);
+    }
+
+    #[test]
+    fn test_transform_id_serde_round_trip_with_separator() {
+        let transform_id = TransformId::new(FrameId::from("a->b"), FrameId::from("c"));
+
+        let serialized = serde_json::to_string(&transform_id).unwrap();
+        let deserialized: TransformId = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(serialized, r#"{"frame_id":"a->b","child_frame_id":"c"}"#);
+        assert_eq!(deserialized, transform_id);
+    }
+
+    #[test]
+    fn test_transform_id_deserialize_object() {
+        let deserialized: TransformId =
+            serde_json::from_str(r#"{ "frame_id": "map", "child_frame_id": "base_link" }"#)
+                .unwrap();
+
+        assert_eq!(
+            deserialized,
+            TransformId::new(FrameId::from("map"), FrameId::from("base_link"))
+        );
+    }
+
+    #[test]
+    fn test_transform_id_deserialize_invalid() {
+        assert!(serde_json::from_str::<TransformId>(r#""map""#).is_err());
+        assert!(serde_json::from_str::<TransformId>(r#""map->map""#).is_err());
+    }
+
+    #[test]
+    fn test_transform_id_reversed() {
+        let transform_id = TransformId::new(FrameId::from("map"), FrameId::from("base_link"));
+
+        let reversed = transform_id.reversed();
+
+        assert_eq!(reversed.frame_id, FrameId::from("base_link"));
+        assert_eq!(reversed.child_frame_id, FrameId::from("map"));
+        assert_eq!(reversed.reversed(), transform_id);
+    }
+
+    #[test]
+    fn test_euler_angles_safe() {
+        let timestamp = Utc.timestamp_opt(1, 0).unwrap();
+        let near_vertical = Transform::new(
+            timestamp,
+            Vector3::zeros(),
+            UnitQuaternion::from_euler_angles(0.3, std::f64::consts::FRAC_PI_2 - 1e-4, 0.2),
+        );
+        let level = Transform::new(
+            timestamp,
+            Vector3::zeros(),
+            UnitQuaternion::from_euler_angles(0.3, 0.5, 0.2),
+        );
+
+        let (_, pitch, _, near_gimbal_lock) = near_vertical.euler_angles_safe();
+        assert!(near_gimbal_lock);
+        assert!((pitch - (std::f64::consts::FRAC_PI_2 - 1e-4)).abs() < 1e-6);
+        assert!(!near_vertical.euler_angles_safe_with_epsilon(1e-5).3);
+
+        let (roll, pitch, yaw, near_gimbal_lock) = level.euler_angles_safe();
+        assert!(!near_gimbal_lock);
+        assert!((roll - 0.3).abs() < 1e-12);
+        assert!((pitch - 0.5).abs() < 1e-12);
+        assert!((yaw - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_identity() {
+        let timestamp = Utc.timestamp_opt(1, 0).unwrap();
+        let shifted = Transform::new(
+            timestamp,
+            Vector3::new(0.0, 0.0, 1e-3),
+            UnitQuaternion::identity(),
+        );
+        let rotated = Transform::new(
+            timestamp,
+            Vector3::zeros(),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, 1e-3),
+        );
+
+        assert!(Transform::identity(timestamp).is_identity(1e-9));
+        assert_eq!(Transform::identity(timestamp).timestamp, timestamp);
+        assert!(!shifted.is_identity(1e-9));
+        assert!(!rotated.is_identity(1e-9));
+        assert!(rotated.is_identity(1e-2));
+    }
+
+    #[test]
+    fn test_transform_point() {
+        // 90° about z followed by a translation
+        let transform = Transform::new(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::new(10.0, 20.0, 30.0),
+            UnitQuaternion::from_axis_angle(&Vector3::z_axis(), std::f64::consts::FRAC_PI_2),
+        );
+
+        let result = transform.transform_point(&Point3::new(1.0, 0.0, 0.0));
+
+        assert!((result - Point3::new(10.0, 21.0, 30.0)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_inverse_translation() {
+        let transform = Transform::new(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::new(1.0, -2.0, 3.0),
+            UnitQuaternion::identity(),
+        );
+
+        let inverse = transform.inverse();
+
+        assert_eq!(inverse.timestamp, transform.timestamp);
+        assert_eq!(inverse.translation, Vector3::new(-1.0, 2.0, -3.0));
+        assert_eq!(inverse.rotation, UnitQuaternion::identity());
+    }
+
+    #[test]
+    fn test_inverse_rotation() {
+        let transform = Transform::new(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::zeros(),
+            UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3),
+        );
+
+        let inverse = transform.inverse();
+
+        assert_eq!(inverse.translation, Vector3::zeros());
+        assert!(
+            (inverse.rotation * transform.rotation).angle_to(&UnitQuaternion::identity()) < 1e-12
+        );
+    }
+
+    #[test]
+    fn test_inverse_combined() {
+        let transform = Transform::new(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::new(4.0, 5.0, -6.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, std::f64::consts::FRAC_PI_2),
+        );
+
+        let inverse = transform.inverse();
+
+        let expected = transform.isometry().inverse();
+        assert!(
+            (inverse.isometry().translation.vector - expected.translation.vector).norm() < 1e-12
+        );
+        assert!(inverse.isometry().rotation.angle_to(&expected.rotation) < 1e-12);
+        assert!(
+            (inverse.isometry() * transform.isometry())
+                .translation
+                .vector
+                .norm()
+                < 1e-12
+        );
+    }
+
+    #[test]
+    fn test_swing_twist() {
+        let twist = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 0.7);
+        let swing = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.3);
+        let transform = Transform::new(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::zeros(),
+            swing * twist,
+        );
+
+        let (result_swing, result_twist) = transform.swing_twist(&Vector3::new(0.0, 0.0, 2.0));
+
+        assert!(result_twist.angle_to(&twist) < 1e-12);
+        assert!(result_swing.angle_to(&swing) < 1e-12);
+        assert!((result_swing * result_twist).angle_to(&transform.rotation) < 1e-12);
+    }
+
+    #[test]
+    fn test_mul() {
+        let rotation = Transform::new(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::zeros(),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, std::f64::consts::FRAC_PI_2),
+        );
+        let translation = Transform::new(
+            Utc.timestamp_opt(2, 0).unwrap(),
+            Vector3::new(1.0, 2.0, 3.0),
+            UnitQuaternion::identity(),
+        );
+
+        let result = &rotation * &translation;
+
+        let expected = rotation.isometry() * translation.isometry();
+        assert_eq!(result.timestamp, rotation.timestamp);
+        assert!((result.translation - expected.translation.vector).norm() < 1e-12);
+        assert!((result.translation - Vector3::new(-2.0, 1.0, 3.0)).norm() < 1e-12);
+        assert!(result.rotation.angle_to(&expected.rotation) < 1e-12);
+        assert_eq!(rotation * translation, result);
+    }
+
+    #[test]
+    fn test_transform_points() {
+        let transform = Transform::new(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::new(1.0, -2.0, 3.0),
+            UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3),
+        );
+        let points: Vec<Point3<f64>> = (0..PARALLEL_POINT_THRESHOLD + 1)
+            .map(|i| Point3::new(i as f64, -(i as f64) * 0.5, 1.0))
+            .collect();
+        let expected: Vec<Point3<f64>> = points
+            .iter()
+            .map(|p| transform.transform_point(p))
+            .collect();
+
+        let result = transform.transform_points(&points);
+        let short_result = transform.transform_points(&points[..3]);
+        let mut result_mut = points.clone();
+        transform.transform_points_mut(&mut result_mut);
+
+        assert_eq!(result, expected);
+        assert_eq!(short_result, expected[..3]);
+        assert_eq!(result_mut, expected);
+        assert_eq!(
+            transform.transform_point(&Point3::origin()),
+            Point3::new(1.0, -2.0, 3.0)
+        );
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let transform = Transform::new(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::new(1.0, 2.0, 3.0),
+            UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3),
+        );
+        let noisy = Transform::new(
+            transform.timestamp,
+            transform.translation + Vector3::new(1e-10, -1e-10, 0.0),
+            UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3 + 1e-10),
+        );
+        let shifted = Transform::new(
+            transform.timestamp,
+            transform.translation + Vector3::new(0.0, 0.0, 1e-3),
+            transform.rotation,
+        );
+
+        assert!(transform.approx_eq(&noisy, 1e-9));
+        assert!(!transform.approx_eq(&shifted, 1e-9));
+        assert!(!transform.approx_eq(&Transform::identity(transform.timestamp), 1e-9));
+    }
+
+    #[test]
+    fn test_approx_eq_ignores_timestamp() {
+        let transform = Transform::new(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::new(1.0, 2.0, 3.0),
+            UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3),
+        );
+        let resampled = Transform::new(
+            Utc.timestamp_opt(2, 500_000_000).unwrap(),
+            transform.translation,
+            transform.rotation,
+        );
+
+        assert!(transform.approx_eq(&resampled, 1e-9));
+    }
+
+    #[test]
+    fn test_approx_eq_quaternion_sign() {
+        let transform = Transform::new(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::new(1.0, 2.0, 3.0),
+            UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3),
+        );
+        let negated = Transform::new(
+            transform.timestamp,
+            transform.translation,
+            UnitQuaternion::new_unchecked(-transform.rotation.into_inner()),
+        );
+
+        assert_ne!(transform.rotation.coords, negated.rotation.coords);
+        assert!(transform.approx_eq(&negated, 1e-9));
+        assert!(negated.approx_eq(&transform, 1e-9));
+    }
+
+    #[test]
+    fn test_euler_deg_round_trip() {
+        let transform = Transform::from_euler_deg(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::new(1.0, 2.0, 3.0),
+            10.0,
+            -20.0,
+            135.0,
+        );
+
+        let (roll, pitch, yaw) = transform.euler_angles_deg();
+        let (roll_rad, pitch_rad, yaw_rad) = transform.euler_angles_rad();
+
+        assert_eq!(transform.translation, Vector3::new(1.0, 2.0, 3.0));
+        assert!((roll - 10.0).abs() < 1e-9);
+        assert!((pitch + 20.0).abs() < 1e-9);
+        assert!((yaw - 135.0).abs() < 1e-9);
+        assert!((yaw_rad - 135.0_f64.to_radians()).abs() < 1e-12);
+        assert!((roll_rad - 10.0_f64.to_radians()).abs() < 1e-12);
+        assert!((pitch_rad + 20.0_f64.to_radians()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_axis_angle() {
+        let timestamp = Utc.timestamp_opt(1, 0).unwrap();
+        let transform = Transform::from_euler_deg(timestamp, Vector3::zeros(), 0.0, 0.0, 90.0);
+
+        let (axis, angle) = transform.axis_angle().unwrap();
+
+        assert!((axis - Vector3::z()).norm() < 1e-12);
+        assert!((angle - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert_eq!(Transform::identity(timestamp).axis_angle(), None);
     }
 }
@@ -1,3 +1,5 @@
+use crate::utils::transforms_interpolation::interpolate_rotation;
+use nalgebra::UnitQuaternion;
 use std::str::FromStr;
 
 /// Additional information for a frame.
@@ -6,12 +8,17 @@ use std::str::FromStr;
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
 pub struct TransformInfo {
     pub interpolation_method: Option<InterpolationMethod>,
+    pub rotation_interpolation: Option<RotationInterpolation>,
 }
 
 impl TransformInfo {
-    pub fn new(interpolation_method: Option<InterpolationMethod>) -> Self {
+    pub fn new(
+        interpolation_method: Option<InterpolationMethod>,
+        rotation_interpolation: Option<RotationInterpolation>,
+    ) -> Self {
         Self {
             interpolation_method,
+            rotation_interpolation,
         }
     }
 }
@@ -26,6 +33,12 @@ pub enum InterpolationMethod {
     Step,
     /// Linear interpolation
     Linear,
+    /// Cubic Hermite interpolation with Catmull-Rom tangents for the translation and squad for the
+    /// rotation
+    ///
+    /// Requires a neighboring sample on each side of the interval and falls back to linear
+    /// interpolation in the first and last interval.
+    CubicHermite,
 }
 
 impl FromStr for InterpolationMethod {
@@ -35,6 +48,7 @@ impl FromStr for InterpolationMethod {
         match input {
             "step" => Ok(InterpolationMethod::Step),
             "linear" => Ok(InterpolationMethod::Linear),
+            "cubic_hermite" => Ok(InterpolationMethod::CubicHermite),
             _ => Err(()),
         }
     }
@@ -45,6 +59,55 @@ impl InterpolationMethod {
         match self {
             InterpolationMethod::Step => "step",
             InterpolationMethod::Linear => "linear",
+            InterpolationMethod::CubicHermite => "cubic_hermite",
         }
     }
 }
+
+/// Methods for interpolating the rotation between two [`Transform`].
+///
+/// [`Transform`]: crate::Transform
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum RotationInterpolation {
+    /// Spherical linear interpolation with constant angular velocity
+    #[default]
+    Slerp,
+    /// Normalized linear interpolation of the quaternion components
+    ///
+    /// Cheaper to compute than [`RotationInterpolation::Slerp`] and exact at both ends and at the
+    /// midpoint. In between, the angular velocity is not constant, so that the angle deviates
+    /// from slerp for large rotations between two samples.
+    Nlerp,
+}
+
+impl FromStr for RotationInterpolation {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<RotationInterpolation, Self::Err> {
+        match input {
+            "slerp" => Ok(RotationInterpolation::Slerp),
+            "nlerp" => Ok(RotationInterpolation::Nlerp),
+            _ => Err(()),
+        }
+    }
+}
+
+impl RotationInterpolation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RotationInterpolation::Slerp => "slerp",
+            RotationInterpolation::Nlerp => "nlerp",
+        }
+    }
+
+    /// Interpolates between two rotations along the shorter arc, whereby a `weight` of zero
+    /// yields `start` and a `weight` of one yields `end`.
+    pub fn interpolate(
+        &self,
+        start: &UnitQuaternion<f64>,
+        end: &UnitQuaternion<f64>,
+        weight: f64,
+    ) -> UnitQuaternion<f64> {
+        interpolate_rotation(start, end, weight, *self)
+    }
+}
@@ -1,5 +1,5 @@
 use crate::Transform;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 
 /// Returns true if all transforms are equal or if there is only one.
 pub fn is_static(transforms: &[Transform]) -> bool {
@@ -9,38 +9,70 @@ pub fn is_static(transforms: &[Transform]) -> bool {
     })
 }
 
+/// Returns true if the transforms are static or their samples span the timestamp.
+pub fn covers_timestamp(transforms: &[Transform], timestamp: &DateTime<Utc>) -> bool {
+    is_static(transforms)
+        || (transforms.first().unwrap().timestamp <= *timestamp
+            && *timestamp <= transforms.last().unwrap().timestamp)
+}
+
 pub fn get_previous_transform(
     transforms: &[Transform],
     timestamp: &DateTime<Utc>,
 ) -> Option<Transform> {
     let previous_timestamps: Vec<&Transform> = transforms
         .iter()
-        .filter(|t| t.timestamp.timestamp_nanos() <= timestamp.timestamp_nanos())
+        .filter(|t| t.timestamp <= *timestamp)
         .collect();
 
     let previous = previous_timestamps
         .iter()
-        .max_by_key(|t| t.timestamp.timestamp_nanos())
+        .max_by_key(|t| t.timestamp)
         .map(|&t| t.clone());
 
     previous
 }
 
-pub fn get_next_transform(
-    transforms: &[Transform],
+/// Returns consecutive pairs of transforms, each spanning an interval.
+pub fn get_intervals(transforms: &[Transform]) -> impl Iterator<Item = (&Transform, &Transform)> {
+    transforms.windows(2).map(|t| (&t[0], &t[1]))
+}
+
+/// Returns the pair of transforms, whose interval contains the timestamp.
+///
+/// The interval is closed at the start and open at the end. Requires the transforms to be sorted
+/// by time and returns `None` if the timestamp is outside of all intervals.
+pub fn get_interval_containing<'a>(
+    transforms: &'a [Transform],
     timestamp: &DateTime<Utc>,
-) -> Option<Transform> {
-    let next_timestamps: Vec<&Transform> = transforms
-        .iter()
-        .filter(|t| timestamp.timestamp_nanos() < t.timestamp.timestamp_nanos())
-        .collect();
+) -> Option<(&'a Transform, &'a Transform)> {
+    let index = transforms.partition_point(|t| t.timestamp <= *timestamp);
+    if index == 0 || index == transforms.len() {
+        return None;
+    }
 
-    let next = next_timestamps
-        .iter()
-        .min_by_key(|t| t.timestamp.timestamp_nanos())
-        .map(|&t| t.clone());
+    Some((&transforms[index - 1], &transforms[index]))
+}
+
+/// Returns the relative position of the timestamp in the interval from `previous` to `next`,
+/// which is `0.0` at `previous` and `1.0` at `next`.
+///
+/// Returns `0.0` for an interval of zero duration, so that `previous` is selected.
+pub fn blend_weight(previous: &Transform, next: &Transform, timestamp: &DateTime<Utc>) -> f64 {
+    let interval_duration = next.timestamp - previous.timestamp;
+    if interval_duration.is_zero() {
+        return 0.0;
+    }
 
-    next
+    duration_seconds(&(*timestamp - previous.timestamp)) / duration_seconds(&interval_duration)
+}
+
+/// Returns the duration in seconds.
+///
+/// In contrast to [`Duration::num_nanoseconds`], this cannot overflow, whereby durations beyond
+/// about 100 days lose nanosecond precision.
+pub fn duration_seconds(duration: &Duration) -> f64 {
+    duration.num_seconds() as f64 + duration.subsec_nanos() as f64 / 1e9
 }
 
 #[cfg(test)]
@@ -69,3 +101,80 @@ mod test_get_previous {
         assert_eq!(result.translation, Vector3::new(0.0, 0.0, 0.0));
     }
 }
+
+#[cfg(test)]
+mod test_intervals {
+    use crate::utils::transform_list_utils::{
+        blend_weight, get_interval_containing, get_intervals,
+    };
+    use crate::Transform;
+    use chrono::{DateTime, TimeZone, Utc};
+    use nalgebra::{UnitQuaternion, Vector3};
+
+    fn transforms() -> Vec<Transform> {
+        [1, 2, 4]
+            .into_iter()
+            .map(|s| {
+                Transform::new(
+                    Utc.timestamp_opt(s, 0).unwrap(),
+                    Vector3::new(s as f64, 0.0, 0.0),
+                    UnitQuaternion::identity(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_intervals() {
+        let transforms = transforms();
+
+        assert_eq!(get_intervals(&transforms).count(), 2);
+        assert_eq!(get_intervals(&transforms[..1]).count(), 0);
+    }
+
+    #[test]
+    fn test_interval_containing() {
+        let transforms = transforms();
+        let start_seconds = |sec: i64, nanosec: u32| {
+            get_interval_containing(&transforms, &Utc.timestamp_opt(sec, nanosec).unwrap())
+                .map(|(a, b)| (a.timestamp.timestamp(), b.timestamp.timestamp()))
+        };
+
+        assert_eq!(start_seconds(0, 0), None);
+        assert_eq!(start_seconds(1, 0), Some((1, 2)));
+        assert_eq!(start_seconds(1, 500), Some((1, 2)));
+        assert_eq!(start_seconds(2, 0), Some((2, 4)));
+        assert_eq!(start_seconds(3, 0), Some((2, 4)));
+        assert_eq!(start_seconds(4, 0), None);
+    }
+
+    #[test]
+    fn test_blend_weight() {
+        let transforms = transforms();
+
+        let weight = blend_weight(
+            &transforms[1],
+            &transforms[2],
+            &Utc.timestamp_opt(3, 0).unwrap(),
+        );
+        let zero_interval_weight = blend_weight(
+            &transforms[1],
+            &transforms[1],
+            &Utc.timestamp_opt(2, 0).unwrap(),
+        );
+
+        assert_eq!(weight, 0.5);
+        assert_eq!(zero_interval_weight, 0.0);
+    }
+
+    #[test]
+    fn test_blend_weight_long_interval() {
+        let previous = Transform::identity(DateTime::<Utc>::MIN_UTC);
+        let next = Transform::identity(DateTime::<Utc>::MAX_UTC);
+        let timestamp = DateTime::<Utc>::MIN_UTC + (next.timestamp - previous.timestamp) / 4;
+
+        let weight = blend_weight(&previous, &next, &timestamp);
+
+        assert!((weight - 0.25).abs() < 1e-12);
+    }
+}
@@ -1,13 +1,16 @@
-use crate::utils::transform_list_utils::{get_next_transform, get_previous_transform, is_static};
+use crate::utils::transform_list_utils::{
+    blend_weight, duration_seconds, get_interval_containing, get_previous_transform, is_static,
+};
 use crate::Error::{MissingTimestamp, MissingTransforms, TransformsNotSortedByTime};
-use crate::{Error, InterpolationMethod, Transform};
-use chrono::{DateTime, Duration, Utc};
-use nalgebra::Isometry3;
+use crate::{Error, InterpolationMethod, RotationInterpolation, Transform};
+use chrono::{DateTime, Utc};
+use nalgebra::{Isometry3, UnitQuaternion};
 
 pub fn interpolate_transforms(
-    transforms: &Vec<Transform>,
+    transforms: &[Transform],
     timestamp: &Option<DateTime<Utc>>,
     method: InterpolationMethod,
+    rotation_interpolation: RotationInterpolation,
 ) -> Result<Isometry3<f64>, Error> {
     if transforms.is_empty() {
         return Err(MissingTransforms());
@@ -35,12 +38,17 @@ pub fn interpolate_transforms(
 
     match method {
         InterpolationMethod::Step => interpolate_step_function(transforms, &timestamp),
-        InterpolationMethod::Linear => interpolate_linearly(transforms, &timestamp),
+        InterpolationMethod::Linear => {
+            interpolate_linearly(transforms, &timestamp, rotation_interpolation)
+        }
+        InterpolationMethod::CubicHermite => {
+            interpolate_cubic_hermite(transforms, &timestamp, rotation_interpolation)
+        }
     }
 }
 
 fn interpolate_step_function(
-    transforms: &Vec<Transform>,
+    transforms: &[Transform],
     timestamp: &DateTime<Utc>,
 ) -> Result<Isometry3<f64>, Error> {
     if transforms.is_empty() {
@@ -59,33 +67,140 @@ fn interpolate_step_function(
 /// If requested [timestamp] is before the first transform in the vector, simply the first
 /// transform is returned.
 fn interpolate_linearly(
-    transforms: &Vec<Transform>,
+    transforms: &[Transform],
     timestamp: &DateTime<Utc>,
+    rotation_interpolation: RotationInterpolation,
 ) -> Result<Isometry3<f64>, Error> {
     if transforms.is_empty() {
         return Err(MissingTransforms());
     }
 
-    let previous_transform = get_previous_transform(transforms, timestamp).unwrap();
-    let next_transform = get_next_transform(transforms, timestamp).unwrap(); //.unwrap_or(transforms.last().unwrap().clone());
+    let (previous_transform, next_transform) =
+        get_interval_containing(transforms, timestamp).ok_or(MissingTransforms())?;
 
-    let duration: Duration = next_transform.timestamp - previous_transform.timestamp;
-    let _duration_num = duration.num_nanoseconds();
-    let first_duration = *timestamp - previous_transform.timestamp;
-    let _first_duration_num = first_duration.num_nanoseconds();
-
-    let weight: f64 = first_duration.num_nanoseconds().unwrap() as f64
-        / duration.num_nanoseconds().unwrap() as f64;
+    let weight = blend_weight(previous_transform, next_transform, timestamp);
 
     let translation =
         previous_transform.translation * (1.0 - weight) + next_transform.translation * weight;
-    let rotation = previous_transform
-        .rotation
-        .slerp(&next_transform.rotation, weight);
+    let rotation = interpolate_rotation(
+        &previous_transform.rotation,
+        &next_transform.rotation,
+        weight,
+        rotation_interpolation,
+    );
     let isometry = Isometry3::from_parts(translation.into(), rotation);
     Ok(isometry)
 }
 
+/// Implements a cubic Hermite interpolation for a vector of transforms.
+///
+/// The translation tangents are the finite differences of the neighboring samples (Catmull-Rom),
+/// which also covers non-uniform sample spacing. The rotation is interpolated with squad. In the
+/// first and last interval, where a neighboring sample is missing, the interpolation is linear.
+fn interpolate_cubic_hermite(
+    transforms: &[Transform],
+    timestamp: &DateTime<Utc>,
+    rotation_interpolation: RotationInterpolation,
+) -> Result<Isometry3<f64>, Error> {
+    let index = transforms.partition_point(|t| t.timestamp <= *timestamp);
+    if index < 2 || transforms.len() <= index + 1 {
+        return interpolate_linearly(transforms, timestamp, rotation_interpolation);
+    }
+    let [before, previous, next, after] =
+        [index - 2, index - 1, index, index + 1].map(|i| &transforms[i]);
+
+    let seconds = |a: &Transform, b: &Transform| duration_seconds(&(b.timestamp - a.timestamp));
+    let interval = seconds(previous, next);
+    let weight = blend_weight(previous, next, timestamp);
+
+    let previous_tangent = (next.translation - before.translation) / seconds(before, next);
+    let next_tangent = (after.translation - previous.translation) / seconds(previous, after);
+    let weight_squared = weight * weight;
+    let weight_cubed = weight_squared * weight;
+    let translation = previous.translation * (2.0 * weight_cubed - 3.0 * weight_squared + 1.0)
+        + previous_tangent * interval * (weight_cubed - 2.0 * weight_squared + weight)
+        + next.translation * (-2.0 * weight_cubed + 3.0 * weight_squared)
+        + next_tangent * interval * (weight_cubed - weight_squared);
+
+    let previous_control =
+        get_squad_control_point(&before.rotation, &previous.rotation, &next.rotation);
+    let next_control = get_squad_control_point(&previous.rotation, &next.rotation, &after.rotation);
+    let rotation = interpolate_rotation(
+        &interpolate_rotation(
+            &previous.rotation,
+            &next.rotation,
+            weight,
+            rotation_interpolation,
+        ),
+        &interpolate_rotation(
+            &previous_control,
+            &next_control,
+            weight,
+            rotation_interpolation,
+        ),
+        2.0 * weight * (1.0 - weight),
+        rotation_interpolation,
+    );
+
+    let isometry = Isometry3::from_parts(translation.into(), rotation);
+    Ok(isometry)
+}
+
+/// Returns the inner control point of squad at the `current` rotation.
+fn get_squad_control_point(
+    previous: &UnitQuaternion<f64>,
+    current: &UnitQuaternion<f64>,
+    next: &UnitQuaternion<f64>,
+) -> UnitQuaternion<f64> {
+    let align = |q: &UnitQuaternion<f64>| {
+        if current.coords.dot(&q.coords) < 0.0 {
+            UnitQuaternion::new_unchecked(-q.into_inner())
+        } else {
+            *q
+        }
+    };
+    let current_inverse = current.inverse();
+    let tangent = ((current_inverse * align(next)).into_inner().ln()
+        + (current_inverse * align(previous)).into_inner().ln())
+        * -0.25;
+
+    current * UnitQuaternion::new_normalize(tangent.exp())
+}
+
+/// Below this sine of the half angle between two rotations, slerp is numerically ill-conditioned
+/// and nlerp is used instead, which is practically exact for such small angles.
+const SLERP_SINE_EPSILON: f64 = 1e-6;
+
+/// Interpolates between two rotations along the shorter arc.
+pub(crate) fn interpolate_rotation(
+    start: &UnitQuaternion<f64>,
+    end: &UnitQuaternion<f64>,
+    weight: f64,
+    rotation_interpolation: RotationInterpolation,
+) -> UnitQuaternion<f64> {
+    match rotation_interpolation {
+        RotationInterpolation::Slerp => start
+            .try_slerp(end, weight, SLERP_SINE_EPSILON)
+            .filter(|r| r.coords.iter().all(|c| c.is_finite()))
+            .unwrap_or_else(|| interpolate_rotation_normalized(start, end, weight)),
+        RotationInterpolation::Nlerp => interpolate_rotation_normalized(start, end, weight),
+    }
+}
+
+fn interpolate_rotation_normalized(
+    start: &UnitQuaternion<f64>,
+    end: &UnitQuaternion<f64>,
+    weight: f64,
+) -> UnitQuaternion<f64> {
+    let end_coords = if start.coords.dot(&end.coords) < 0.0 {
+        -end.coords
+    } else {
+        end.coords
+    };
+    let coords = start.coords * (1.0 - weight) + end_coords * weight;
+    UnitQuaternion::from_quaternion(coords.into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,7 +221,8 @@ mod tests {
         );
         let transforms: Vec<Transform> = vec![transform_a, transform_b]; // : &Vec<Transform>, timestamp: &DateTime<Utc>
         let timestamp: DateTime<Utc> = Utc.timestamp_opt(2, 2000).unwrap();
-        let result = interpolate_linearly(&transforms, &timestamp).unwrap();
+        let result =
+            interpolate_linearly(&transforms, &timestamp, RotationInterpolation::Slerp).unwrap();
 
         assert_eq!(result.translation, Translation3::new(1.0, 2.0, -3.0));
         assert_eq!(
@@ -114,4 +230,74 @@ mod tests {
             (std::f64::consts::FRAC_PI_2, 0.0, 0.0)
         );
     }
+
+    #[test]
+    fn test_nlerp_compared_to_slerp() {
+        let start = UnitQuaternion::identity();
+        let end = UnitQuaternion::from_euler_angles(0.0, 0.0, 160f64.to_radians());
+
+        let slerp_midpoint = interpolate_rotation(&start, &end, 0.5, RotationInterpolation::Slerp);
+        let nlerp_midpoint = interpolate_rotation(&start, &end, 0.5, RotationInterpolation::Nlerp);
+        let slerp_quarter = interpolate_rotation(&start, &end, 0.25, RotationInterpolation::Slerp);
+        let nlerp_quarter = interpolate_rotation(&start, &end, 0.25, RotationInterpolation::Nlerp);
+
+        // both agree at the midpoint, due to symmetry
+        assert!(slerp_midpoint.angle_to(&nlerp_midpoint) < 1e-12);
+        assert!((slerp_midpoint.angle() - 80f64.to_radians()).abs() < 1e-12);
+        // in between, nlerp deviates by several degrees for large angles
+        let quarter_deviation = slerp_quarter.angle_to(&nlerp_quarter).to_degrees();
+        assert!(1.0 < quarter_deviation && quarter_deviation < 10.0);
+    }
+
+    #[test]
+    fn test_slerp_near_antipodal() {
+        let start = UnitQuaternion::from_euler_angles(0.2, 0.4, 0.6);
+        let perturbation = UnitQuaternion::from_euler_angles(0.0, 0.0, 1e-9);
+        // represents almost the same rotation, but with a quaternion dot product of almost -1
+        let end = UnitQuaternion::new_unchecked(-(perturbation * start).into_inner());
+
+        for weight in [0.0, 0.25, 0.5, 1.0] {
+            let result = interpolate_rotation(&start, &end, weight, RotationInterpolation::Slerp);
+
+            assert!(result.coords.iter().all(|c| c.is_finite()));
+            assert!((result.coords.norm() - 1.0).abs() < 1e-12);
+            assert!(result.angle_to(&start) < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_cubic_hermite_interpolation() {
+        // quadratic translation and constant angular velocity, which are reproduced exactly
+        let transforms: Vec<Transform> = (0..5)
+            .map(|i| {
+                Transform::new(
+                    Utc.timestamp_opt(i, 0).unwrap(),
+                    Vector3::new((i * i) as f64, i as f64, 0.0),
+                    UnitQuaternion::from_euler_angles(0.0, 0.0, 0.3 * i as f64),
+                )
+            })
+            .collect();
+
+        let result = interpolate_transforms(
+            &transforms,
+            &Some(Utc.timestamp_opt(1, 500_000_000).unwrap()),
+            InterpolationMethod::CubicHermite,
+            RotationInterpolation::Slerp,
+        )
+        .unwrap();
+        let first_interval_result = interpolate_transforms(
+            &transforms,
+            &Some(Utc.timestamp_opt(0, 500_000_000).unwrap()),
+            InterpolationMethod::CubicHermite,
+            RotationInterpolation::Slerp,
+        )
+        .unwrap();
+
+        assert!((result.translation.vector - Vector3::new(2.25, 1.5, 0.0)).norm() < 1e-12);
+        assert!((result.rotation.angle() - 0.45).abs() < 1e-12);
+        // linear in the first interval
+        assert!(
+            (first_interval_result.translation.vector - Vector3::new(0.5, 0.5, 0.0)).norm() < 1e-12
+        );
+    }
 }
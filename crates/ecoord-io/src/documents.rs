@@ -1,19 +1,43 @@
 use chrono::{DateTime, Duration, TimeZone, Timelike, Utc};
 use nalgebra::{Quaternion, UnitQuaternion, Vector3};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub(crate) struct EcoordDocument {
-    pub transforms: Vec<TransformElement>,
-    pub frame_info: Vec<FrameInfoElement>,
+pub(crate) struct EcoordDocument<T = TransformElement, F = FrameInfoElement> {
+    pub transforms: Vec<T>,
+    pub frame_info: Vec<F>,
     pub channel_info: Vec<ChannelInfoElement>,
     pub transform_info: Vec<TransformInfoElement>,
 }
 
+/// Document, which additionally keeps the unknown fields of the document, its transforms and its
+/// frame infos.
+///
+/// Only used if the unknown fields are requested, since flattening buffers each element before
+/// deserializing it.
+pub(crate) type ExtendedEcoordDocument = WithExtraFields<
+    EcoordDocument<WithExtraFields<TransformElement>, WithExtraFields<FrameInfoElement>>,
+>;
+
+/// Element together with its fields unknown to this version, which are kept for writing them
+/// back.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct WithExtraFields<T> {
+    #[serde(flatten)]
+    pub element: T,
+    #[serde(flatten)]
+    pub extra_fields: Map<String, Value>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub(crate) struct FrameInfoElement {
     pub id: String,
     pub crs_epsg: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crs_wkt: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_rotation: Option<QuaternionElement>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -27,6 +51,8 @@ pub(crate) struct TransformInfoElement {
     pub frame_id: String,
     pub child_frame_id: String,
     pub interpolation_method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotation_interpolation: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -38,7 +64,7 @@ pub struct TransformElement {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<DurationElement>,
     pub translation: VectorElement,
-    pub rotation: QuaternionElement,
+    pub rotation: RotationElement,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
@@ -106,6 +132,74 @@ impl From<VectorElement> for Vector3<f64> {
     }
 }
 
+/// Rotation given either as a quaternion or as an axis-angle rotation vector.
+///
+/// Both representations must not be combined in a single object, while other fields, such as
+/// annotations, are ignored.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+#[serde(untagged, try_from = "RawRotationElement")]
+pub enum RotationElement {
+    Quaternion(QuaternionElement),
+    AxisAngle(AxisAngleElement),
+}
+
+/// All fields a rotation object may contain, which are validated when converting to a
+/// [`RotationElement`].
+#[derive(Deserialize, Debug, Copy, Clone)]
+struct RawRotationElement {
+    x: Option<f64>,
+    y: Option<f64>,
+    z: Option<f64>,
+    w: Option<f64>,
+    axis_angle: Option<[f64; 3]>,
+}
+
+impl TryFrom<RawRotationElement> for RotationElement {
+    type Error = String;
+
+    fn try_from(item: RawRotationElement) -> Result<Self, Self::Error> {
+        match item {
+            RawRotationElement {
+                x: Some(x),
+                y: Some(y),
+                z: Some(z),
+                w: Some(w),
+                axis_angle: None,
+            } => Ok(Self::Quaternion(QuaternionElement { x, y, z, w })),
+            RawRotationElement {
+                x: None,
+                y: None,
+                z: None,
+                w: None,
+                axis_angle: Some(axis_angle),
+            } => Ok(Self::AxisAngle(AxisAngleElement { axis_angle })),
+            RawRotationElement {
+                axis_angle: Some(_),
+                ..
+            } => Err("rotation must not combine quaternion fields and `axis_angle`".to_string()),
+            _ => Err(
+                "rotation requires either all quaternion fields `x`, `y`, `z`, `w` or `axis_angle`"
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+impl From<UnitQuaternion<f64>> for RotationElement {
+    fn from(item: UnitQuaternion<f64>) -> Self {
+        Self::Quaternion(item.into())
+    }
+}
+
+impl From<RotationElement> for UnitQuaternion<f64> {
+    fn from(item: RotationElement) -> Self {
+        match item {
+            RotationElement::Quaternion(q) => q.into(),
+            RotationElement::AxisAngle(a) => a.into(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 pub struct QuaternionElement {
     pub x: f64,
@@ -137,3 +231,16 @@ impl From<QuaternionElement> for UnitQuaternion<f64> {
         Self::from_quaternion(quaternion)
     }
 }
+
+/// Rotation vector, whose direction is the rotation axis and whose magnitude is the angle in
+/// radians.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct AxisAngleElement {
+    pub axis_angle: [f64; 3],
+}
+
+impl From<AxisAngleElement> for UnitQuaternion<f64> {
+    fn from(item: AxisAngleElement) -> Self {
+        Self::from_scaled_axis(Vector3::from(item.axis_angle))
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -13,4 +14,21 @@ pub enum Error {
     NoFileExtension(),
     #[error("file extension `{0}` is invalid")]
     InvalidFileExtension(String),
+    #[error("npy file is invalid: {0}")]
+    InvalidNpyFile(String),
+    #[error("pose matrix at index {0} is not rigid")]
+    InvalidPoseMatrix(usize),
+    #[error("number of poses ({poses}) does not match the number of timestamps ({timestamps})")]
+    PoseCountMismatch { poses: usize, timestamps: usize },
+    #[error("line {0} is not a valid TUM trajectory entry")]
+    InvalidTumLine(usize),
+    #[error("interpolation method `{0}` is unknown")]
+    InvalidInterpolationMethod(String),
+    #[error("rotation interpolation `{0}` is unknown")]
+    InvalidRotationInterpolation(String),
+    #[error("start `{start}` of the time range must not be after its end `{end}`")]
+    InvalidTimeRange {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
 }
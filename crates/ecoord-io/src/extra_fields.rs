@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use ecoord_core::{ChannelId, FrameId, TransformId};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Fields of a document, which are unknown to this version and kept for writing them back.
+///
+/// Only the fields of the document itself, its transforms and its frame infos are kept. Unknown
+/// fields of nested objects, such as a transform's `rotation` or `timestamp`, are dropped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExtraFields {
+    /// Unknown top-level fields of the document.
+    pub document: Map<String, Value>,
+    /// Unknown fields of the transform elements by channel, transform and timestamp of the
+    /// sample.
+    pub transforms: HashMap<(ChannelId, TransformId, DateTime<Utc>), Map<String, Value>>,
+    /// Unknown fields of the frame info elements by frame.
+    pub frame_info: HashMap<FrameId, Map<String, Value>>,
+}
@@ -1,17 +1,37 @@
 mod documents;
 mod error;
+mod extra_fields;
+mod load;
+mod npy;
 mod read;
 mod read_impl;
+mod timeline;
+mod tum;
 mod write;
 mod write_impl;
 
 #[doc(inline)]
 pub use crate::error::Error;
 
+#[doc(inline)]
+pub use crate::extra_fields::ExtraFields;
+
 #[doc(inline)]
 pub use crate::read::EcoordReader;
 
 #[doc(inline)]
 pub use crate::write::EcoordWriter;
 
+#[doc(inline)]
+pub use crate::load::load_all;
+
+#[doc(inline)]
+pub use crate::timeline::export_timeline_csv;
+
+#[doc(inline)]
+pub use crate::npy::read_npy_poses;
+
+#[doc(inline)]
+pub use crate::tum::read_tum_trajectory;
+
 pub const FILE_EXTENSION_ECOORD_FORMAT: &str = "json";
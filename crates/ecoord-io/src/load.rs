@@ -0,0 +1,81 @@
+use crate::error::Error;
+use crate::EcoordReader;
+use ecoord_core::ReferenceFrames;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Infix of the file names, which are considered ecoord documents.
+const ECOORD_FILE_NAME_INFIX: &str = ".ecoord.";
+
+/// Path of a file together with the result of reading it.
+type FileResult = (PathBuf, Result<ReferenceFrames, Error>);
+
+/// Reads every `*.ecoord.*` file in the directory and returns a result per file.
+///
+/// A file failing to read does not abort the others, so that a pipeline can log and skip it. The
+/// results are sorted by path. Only an error while listing the directory is returned directly.
+pub fn load_all(directory_path: impl AsRef<Path>) -> Result<Vec<FileResult>, Error> {
+    let mut file_paths: Vec<PathBuf> = fs::read_dir(directory_path)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    file_paths.retain(|p| {
+        p.is_file()
+            && p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.contains(ECOORD_FILE_NAME_INFIX))
+    });
+    file_paths.sort();
+
+    let results = file_paths
+        .into_iter()
+        .map(|p| {
+            let result = EcoordReader::from_path(&p).and_then(|r| r.finish());
+            (p, result)
+        })
+        .collect();
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_all() {
+        let directory_path =
+            std::env::temp_dir().join(format!("ecoord_load_all_{}", std::process::id()));
+        fs::create_dir_all(&directory_path).unwrap();
+        fs::write(
+            directory_path.join("valid.ecoord.json"),
+            r#"{
+                "transforms": [{
+                    "channel_id": "calibration",
+                    "frame_id": "base_link",
+                    "child_frame_id": "lidar",
+                    "timestamp": { "sec": 0, "nanosec": 0 },
+                    "translation": { "x": 1.0, "y": 0.0, "z": 0.0 },
+                    "rotation": { "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 }
+                }],
+                "frame_info": [],
+                "channel_info": [],
+                "transform_info": []
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            directory_path.join("malformed.ecoord.json"),
+            "{ \"transforms\": [",
+        )
+        .unwrap();
+        fs::write(directory_path.join("notes.txt"), "not an ecoord document").unwrap();
+
+        let results = load_all(&directory_path).unwrap();
+        fs::remove_dir_all(&directory_path).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].0.ends_with("malformed.ecoord.json"));
+        assert!(matches!(results[0].1, Err(Error::Parsing(_))));
+        assert!(results[1].0.ends_with("valid.ecoord.json"));
+        assert_eq!(results[1].1.as_ref().unwrap().total_sample_count(), 1);
+    }
+}
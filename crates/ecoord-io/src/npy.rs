@@ -0,0 +1,256 @@
+use crate::error::Error;
+use crate::Error::{InvalidNpyFile, InvalidPoseMatrix, PoseCountMismatch};
+use chrono::{DateTime, Utc};
+use ecoord_core::{ChannelId, FrameId, ReferenceFrames, Transform, TransformId};
+use nalgebra::{Matrix3, Matrix4, Rotation3, UnitQuaternion, Vector3};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+const RIGIDITY_TOLERANCE: f64 = 1e-6;
+
+/// Reads an `(N, 4, 4)` array of homogeneous pose matrices from a `.npy` file as the transforms
+/// of a single transform id.
+///
+/// Only little-endian `f8` and `f4` arrays in C order are supported. Each matrix must be rigid,
+/// i.e. have an orthonormal rotation part with a determinant of one and a last row of
+/// `[0, 0, 0, 1]`. The `timestamps` are assigned to the poses in order.
+pub fn read_npy_poses(
+    path: impl AsRef<Path>,
+    channel_id: ChannelId,
+    frame_id: FrameId,
+    child_frame_id: FrameId,
+    timestamps: &[DateTime<Utc>],
+) -> Result<ReferenceFrames, Error> {
+    let bytes = fs::read(path)?;
+    let matrices = parse_pose_matrices(&bytes)?;
+    if matrices.len() != timestamps.len() {
+        return Err(PoseCountMismatch {
+            poses: matrices.len(),
+            timestamps: timestamps.len(),
+        });
+    }
+
+    let transforms: Vec<Transform> = matrices
+        .iter()
+        .zip(timestamps)
+        .enumerate()
+        .map(|(index, (matrix, timestamp))| {
+            rigid_transform_from_matrix(matrix, *timestamp).ok_or(InvalidPoseMatrix(index))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let transforms = HashMap::from([(
+        (channel_id, TransformId::new(frame_id, child_frame_id)),
+        transforms,
+    )]);
+    let reference_frames =
+        ReferenceFrames::new(transforms, HashMap::new(), HashMap::new(), HashMap::new())?;
+    Ok(reference_frames)
+}
+
+fn parse_pose_matrices(bytes: &[u8]) -> Result<Vec<Matrix4<f64>>, Error> {
+    let invalid = |reason: &str| InvalidNpyFile(reason.to_string());
+
+    if !bytes.starts_with(NPY_MAGIC) || bytes.len() < 10 {
+        return Err(invalid("missing magic string"));
+    }
+    let major_version = bytes[6];
+    let (header_length, header_start) = match major_version {
+        1 => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+        2 | 3 if bytes.len() >= 12 => (
+            u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize,
+            12,
+        ),
+        _ => return Err(invalid("unsupported version")),
+    };
+    let header = bytes
+        .get(header_start..header_start + header_length)
+        .and_then(|h| std::str::from_utf8(h).ok())
+        .ok_or_else(|| invalid("invalid header"))?;
+    let data = &bytes[header_start + header_length..];
+
+    let descr = header_value(header, "descr").ok_or_else(|| invalid("missing descr"))?;
+    let item_size = match descr.trim_matches(|c| c == '\'' || c == '"') {
+        "<f8" => 8,
+        "<f4" => 4,
+        _ => return Err(invalid("only little-endian f8 and f4 arrays are supported")),
+    };
+    if header_value(header, "fortran_order") != Some("False") {
+        return Err(invalid("only arrays in C order are supported"));
+    }
+    let shape: Vec<usize> = header_value(header, "shape")
+        .ok_or_else(|| invalid("missing shape"))?
+        .trim_matches(|c| c == '(' || c == ')')
+        .split(',')
+        .map(|d| d.trim())
+        .filter(|d| !d.is_empty())
+        .map(|d| d.parse())
+        .collect::<Result<_, _>>()
+        .map_err(|_| invalid("invalid shape"))?;
+    let [pose_count, 4, 4] = shape[..] else {
+        return Err(invalid("shape must be (N, 4, 4)"));
+    };
+    if data.len() != pose_count * 16 * item_size {
+        return Err(invalid("data length does not match shape"));
+    }
+
+    let values: Vec<f64> = data
+        .chunks_exact(item_size)
+        .map(|c| match item_size {
+            8 => f64::from_le_bytes(c.try_into().unwrap()),
+            _ => f32::from_le_bytes(c.try_into().unwrap()) as f64,
+        })
+        .collect();
+    let matrices = values
+        .chunks_exact(16)
+        .map(Matrix4::from_row_slice)
+        .collect();
+    Ok(matrices)
+}
+
+/// Returns the value of a key in the Python dict literal of a npy header.
+fn header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let start = header.find(&format!("'{key}'"))? + key.len() + 2;
+    let value = header[start..].trim_start().strip_prefix(':')?.trim_start();
+    let end = if value.starts_with('(') {
+        value.find(')')? + 1
+    } else {
+        value.find([',', '}'])?
+    };
+    Some(value[..end].trim())
+}
+
+fn rigid_transform_from_matrix(
+    matrix: &Matrix4<f64>,
+    timestamp: DateTime<Utc>,
+) -> Option<Transform> {
+    let rotation_matrix: Matrix3<f64> = matrix.fixed_view::<3, 3>(0, 0).into_owned();
+    let is_orthonormal = (rotation_matrix.transpose() * rotation_matrix).relative_eq(
+        &Matrix3::identity(),
+        RIGIDITY_TOLERANCE,
+        RIGIDITY_TOLERANCE,
+    );
+    let is_proper = (rotation_matrix.determinant() - 1.0).abs() <= RIGIDITY_TOLERANCE;
+    let is_homogeneous = matrix
+        .row(3)
+        .iter()
+        .zip([0.0, 0.0, 0.0, 1.0])
+        .all(|(a, b)| (a - b).abs() <= RIGIDITY_TOLERANCE);
+    if !is_orthonormal || !is_proper || !is_homogeneous {
+        return None;
+    }
+
+    let translation: Vector3<f64> = matrix.fixed_view::<3, 1>(0, 3).into_owned();
+    let rotation =
+        UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(rotation_matrix));
+    Some(Transform::new(timestamp, translation, rotation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn npy_bytes(matrices: &[[f64; 16]]) -> Vec<u8> {
+        let mut header = format!(
+            "{{'descr': '<f8', 'fortran_order': False, 'shape': ({}, 4, 4), }}",
+            matrices.len()
+        );
+        while (10 + header.len() + 1) % 64 != 0 {
+            header.push(' ');
+        }
+        header.push('\n');
+
+        let mut bytes = NPY_MAGIC.to_vec();
+        bytes.extend([1, 0]);
+        bytes.extend((header.len() as u16).to_le_bytes());
+        bytes.extend(header.as_bytes());
+        bytes.extend(matrices.iter().flatten().flat_map(|v| v.to_le_bytes()));
+        bytes
+    }
+
+    #[test]
+    fn test_parse_pose_matrices() {
+        let identity = [
+            1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        ];
+        let shifted = [
+            0.0, -1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 2.0, 0.0, 0.0, 1.0, 3.0, 0.0, 0.0, 0.0, 1.0,
+        ];
+        let bytes = npy_bytes(&[identity, shifted]);
+        let timestamps = [
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Utc.timestamp_opt(2, 0).unwrap(),
+        ];
+
+        let matrices = parse_pose_matrices(&bytes).unwrap();
+        let transforms: Vec<Transform> = matrices
+            .iter()
+            .zip(timestamps)
+            .map(|(m, t)| rigid_transform_from_matrix(m, t).unwrap())
+            .collect();
+
+        assert_eq!(transforms.len(), 2);
+        assert_eq!(transforms[0].translation, Vector3::zeros());
+        assert_eq!(transforms[0].rotation, UnitQuaternion::identity());
+        assert_eq!(transforms[1].timestamp, timestamps[1]);
+        assert_eq!(transforms[1].translation, Vector3::new(1.0, 2.0, 3.0));
+        assert!((transforms[1].rotation.angle() - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_parse_pose_matrices_non_rigid() {
+        let scaled = [
+            2.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        ];
+        let bytes = npy_bytes(&[scaled]);
+
+        let matrices = parse_pose_matrices(&bytes).unwrap();
+
+        assert!(
+            rigid_transform_from_matrix(&matrices[0], Utc.timestamp_opt(1, 0).unwrap()).is_none()
+        );
+    }
+
+    #[test]
+    fn test_read_npy_poses() {
+        let shifted = [
+            0.0, -1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 2.0, 0.0, 0.0, 1.0, 3.0, 0.0, 0.0, 0.0, 1.0,
+        ];
+        let file_path =
+            std::env::temp_dir().join(format!("ecoord_read_npy_poses_{}.npy", std::process::id()));
+        fs::write(&file_path, npy_bytes(&[shifted])).unwrap();
+        let timestamps = [Utc.timestamp_opt(1, 0).unwrap()];
+        let read = |timestamps: &[DateTime<Utc>]| {
+            read_npy_poses(
+                &file_path,
+                ChannelId::from("odometry"),
+                FrameId::from("world"),
+                FrameId::from("camera"),
+                timestamps,
+            )
+        };
+
+        let reference_frames = read(&timestamps).unwrap();
+        let mismatch = read(&[timestamps[0], Utc.timestamp_opt(2, 0).unwrap()]);
+        fs::remove_file(&file_path).unwrap();
+
+        let transforms = &reference_frames.transforms()[&(
+            ChannelId::from("odometry"),
+            TransformId::new(FrameId::from("world"), FrameId::from("camera")),
+        )];
+        assert_eq!(reference_frames.transforms().len(), 1);
+        assert_eq!(transforms.len(), 1);
+        assert_eq!(transforms[0].timestamp, timestamps[0]);
+        assert_eq!(transforms[0].translation, Vector3::new(1.0, 2.0, 3.0));
+        assert!(matches!(
+            mismatch,
+            Err(PoseCountMismatch {
+                poses: 1,
+                timestamps: 2
+            })
+        ));
+    }
+}
@@ -1,7 +1,11 @@
 use crate::error::Error;
-use crate::read_impl::read_from_json_file;
+use crate::read_impl::{
+    read_all_from_json_file, read_from_json_file, read_from_json_file_with_extra_fields,
+};
 use crate::Error::{InvalidFileExtension, NoFileExtension};
+use crate::ExtraFields;
 use crate::FILE_EXTENSION_ECOORD_FORMAT;
+use chrono::{DateTime, Utc};
 use ecoord_core::ReferenceFrames;
 use std::fs::File;
 use std::io::Read;
@@ -12,15 +16,58 @@ use std::path::Path;
 #[derive(Debug, Clone)]
 pub struct EcoordReader<R: Read> {
     reader: R,
+    dedup_tolerance: Option<(f64, f64)>,
+    time_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
 }
 
 impl<R: Read> EcoordReader<R> {
     pub fn new(reader: R) -> Self {
-        Self { reader }
+        Self {
+            reader,
+            dedup_tolerance: None,
+            time_range: None,
+        }
+    }
+
+    /// Collapses runs of consecutive samples, which deviate from the run's first sample by at most
+    /// `translation_eps` in translation and `angle_eps` radians in rotation, to their endpoints.
+    ///
+    /// By default, all samples are kept.
+    pub fn with_dedup_tolerance(mut self, translation_eps: f64, angle_eps: f64) -> Self {
+        self.dedup_tolerance = Some((translation_eps, angle_eps));
+        self
+    }
+
+    /// Keeps only the samples of time-dependent transforms within the inclusive time range.
+    ///
+    /// Transforms with a single sample are kept, while time-dependent transforms without samples
+    /// in the range are dropped together with the info of frames no longer referenced. The
+    /// filtering is applied to the output after parsing, so that the whole document is still
+    /// held in memory while reading. Reading fails with [`Error::InvalidTimeRange`], if `start` is
+    /// after `end`.
+    pub fn with_time_range(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.time_range = Some((start, end));
+        self
     }
 
     pub fn finish(self) -> Result<ReferenceFrames, Error> {
-        read_from_json_file(self.reader)
+        read_from_json_file(self.reader, self.dedup_tolerance, self.time_range)
+    }
+
+    /// Reads a document with a top-level array of reference frames, e.g. a sequence of
+    /// calibrations.
+    pub fn finish_all(self) -> Result<Vec<ReferenceFrames>, Error> {
+        read_all_from_json_file(self.reader, self.dedup_tolerance, self.time_range)
+    }
+
+    /// Reads the reference frames and additionally returns the fields of the document, its
+    /// transforms and its frame infos, which are unknown to this version.
+    ///
+    /// The fields can be passed to [`EcoordWriter::with_extra_fields`] to preserve them.
+    ///
+    /// [`EcoordWriter::with_extra_fields`]: crate::EcoordWriter::with_extra_fields
+    pub fn finish_with_extra_fields(self) -> Result<(ReferenceFrames, ExtraFields), Error> {
+        read_from_json_file_with_extra_fields(self.reader, self.dedup_tolerance, self.time_range)
     }
 }
 
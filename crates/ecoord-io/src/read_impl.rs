@@ -1,8 +1,12 @@
-use crate::documents::EcoordDocument;
+use crate::documents::{EcoordDocument, ExtendedEcoordDocument};
 use crate::error::Error;
+use crate::Error::{InvalidInterpolationMethod, InvalidRotationInterpolation, InvalidTimeRange};
+use crate::ExtraFields;
+use chrono::{DateTime, Utc};
+use ecoord_core::Error::CyclicTransformGraph;
 use ecoord_core::{
-    ChannelId, ChannelInfo, FrameId, FrameInfo, InterpolationMethod, ReferenceFrames, Transform,
-    TransformId, TransformInfo,
+    ChannelId, ChannelInfo, FrameId, FrameInfo, InterpolationMethod, ReferenceFrames,
+    RotationInterpolation, Transform, TransformId, TransformInfo,
 };
 use std::collections::HashMap;
 
@@ -12,11 +16,114 @@ use std::str::FromStr;
 
 /// Read a pose from a json file.
 ///
-pub fn read_from_json_file<R: Read>(reader: R) -> Result<ReferenceFrames, Error> {
+pub fn read_from_json_file<R: Read>(
+    reader: R,
+    dedup_tolerance: Option<(f64, f64)>,
+    time_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+) -> Result<ReferenceFrames, Error> {
+    validate_time_range(time_range)?;
     let ecoord_document: EcoordDocument = serde_json::from_reader(reader)?;
+    convert_document(ecoord_document, dedup_tolerance, time_range)
+}
+
+/// Read a pose from a json file and additionally return the unknown fields.
+///
+pub fn read_from_json_file_with_extra_fields<R: Read>(
+    reader: R,
+    dedup_tolerance: Option<(f64, f64)>,
+    time_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+) -> Result<(ReferenceFrames, ExtraFields), Error> {
+    validate_time_range(time_range)?;
+    let extended_document: ExtendedEcoordDocument = serde_json::from_reader(reader)?;
+    let (ecoord_document, extra_fields) = split_extra_fields(extended_document);
+    let reference_frames = convert_document(ecoord_document, dedup_tolerance, time_range)?;
+    Ok((reference_frames, extra_fields))
+}
+
+/// Read multiple poses from a json file containing a top-level array of documents.
+///
+pub fn read_all_from_json_file<R: Read>(
+    reader: R,
+    dedup_tolerance: Option<(f64, f64)>,
+    time_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+) -> Result<Vec<ReferenceFrames>, Error> {
+    validate_time_range(time_range)?;
+    let ecoord_documents: Vec<EcoordDocument> = serde_json::from_reader(reader)?;
+    ecoord_documents
+        .into_iter()
+        .map(|d| convert_document(d, dedup_tolerance, time_range))
+        .collect()
+}
+
+fn validate_time_range(time_range: Option<(DateTime<Utc>, DateTime<Utc>)>) -> Result<(), Error> {
+    match time_range {
+        Some((start, end)) if end < start => Err(InvalidTimeRange { start, end }),
+        _ => Ok(()),
+    }
+}
+
+/// Separates the unknown fields from the document.
+fn split_extra_fields(extended_document: ExtendedEcoordDocument) -> (EcoordDocument, ExtraFields) {
+    let mut extra_fields = ExtraFields {
+        document: extended_document.extra_fields,
+        ..Default::default()
+    };
+    let document = extended_document.element;
 
+    let transforms = document
+        .transforms
+        .into_iter()
+        .map(|t| {
+            let element = t.element;
+            // self-referential transforms are rejected when converting the document
+            if !t.extra_fields.is_empty() && element.frame_id != element.child_frame_id {
+                extra_fields.transforms.insert(
+                    (
+                        ChannelId::from(element.channel_id.as_str()),
+                        TransformId::new(
+                            element.frame_id.as_str().into(),
+                            element.child_frame_id.as_str().into(),
+                        ),
+                        element.timestamp.into(),
+                    ),
+                    t.extra_fields,
+                );
+            }
+            element
+        })
+        .collect();
+    let frame_info = document
+        .frame_info
+        .into_iter()
+        .map(|f| {
+            if !f.extra_fields.is_empty() {
+                extra_fields
+                    .frame_info
+                    .insert(f.element.id.as_str().into(), f.extra_fields);
+            }
+            f.element
+        })
+        .collect();
+
+    let ecoord_document = EcoordDocument {
+        transforms,
+        frame_info,
+        channel_info: document.channel_info,
+        transform_info: document.transform_info,
+    };
+    (ecoord_document, extra_fields)
+}
+
+fn convert_document(
+    ecoord_document: EcoordDocument,
+    dedup_tolerance: Option<(f64, f64)>,
+    time_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+) -> Result<ReferenceFrames, Error> {
     let mut transforms: HashMap<(ChannelId, TransformId), Vec<Transform>> = HashMap::new();
     for current_transform_element in ecoord_document.transforms {
+        if current_transform_element.frame_id == current_transform_element.child_frame_id {
+            return Err(CyclicTransformGraph(current_transform_element.frame_id.into()).into());
+        }
         let current_transform_id = (
             ChannelId::from(current_transform_element.channel_id),
             TransformId::new(
@@ -30,17 +137,46 @@ pub fn read_from_json_file<R: Read>(reader: R) -> Result<ReferenceFrames, Error>
             current_transform_element.translation.into(),
             current_transform_element.rotation.into(),
         );
-
         transforms
             .entry(current_transform_id)
             .or_default()
             .push(current_transform);
     }
+    if let Some((start, end)) = time_range {
+        transforms
+            .values_mut()
+            .filter(|t| t.len() > 1)
+            .for_each(|t| {
+                t.retain(|t| start <= t.timestamp && t.timestamp <= end);
+            });
+        transforms.retain(|_, t| !t.is_empty());
+    }
+    if let Some((translation_eps, angle_eps)) = dedup_tolerance {
+        transforms.values_mut().for_each(|t| {
+            *t = collapse_stationary_runs(std::mem::take(t), translation_eps, angle_eps)
+        });
+    }
 
     let frame_info: HashMap<FrameId, FrameInfo> = ecoord_document
         .frame_info
         .iter()
-        .map(|f| (f.id.clone().into(), FrameInfo::new(f.crs_epsg)))
+        .filter(|f| {
+            let frame_id = FrameId::from(f.id.as_str());
+            time_range.is_none()
+                || transforms
+                    .keys()
+                    .any(|(_, t)| t.frame_id == frame_id || t.child_frame_id == frame_id)
+        })
+        .map(|f| {
+            (
+                f.id.clone().into(),
+                FrameInfo {
+                    crs_epsg: f.crs_epsg,
+                    crs_wkt: f.crs_wkt.clone(),
+                    display_rotation: f.display_rotation.map(|r| r.into()),
+                },
+            )
+        })
         .collect();
 
     let channel_info: HashMap<ChannelId, ChannelInfo> = ecoord_document
@@ -55,16 +191,323 @@ pub fn read_from_json_file<R: Read>(reader: R) -> Result<ReferenceFrames, Error>
         .map(|f| {
             let interpolation_method: Option<InterpolationMethod> = f
                 .interpolation_method
-                .clone()
-                .map(|i| InterpolationMethod::from_str(&i).unwrap());
-            (
+                .as_ref()
+                .map(|i| {
+                    InterpolationMethod::from_str(i)
+                        .map_err(|_| InvalidInterpolationMethod(i.clone()))
+                })
+                .transpose()?;
+            let rotation_interpolation: Option<RotationInterpolation> = f
+                .rotation_interpolation
+                .as_ref()
+                .map(|i| {
+                    RotationInterpolation::from_str(i)
+                        .map_err(|_| InvalidRotationInterpolation(i.clone()))
+                })
+                .transpose()?;
+            if f.frame_id == f.child_frame_id {
+                return Err(CyclicTransformGraph(f.frame_id.clone().into()).into());
+            }
+            Ok((
                 TransformId::new(f.frame_id.clone().into(), f.child_frame_id.clone().into()),
-                TransformInfo::new(interpolation_method),
-            )
+                TransformInfo::new(interpolation_method, rotation_interpolation),
+            ))
         })
-        .collect();
+        .collect::<Result<_, Error>>()?;
 
     let reference_frames =
         ReferenceFrames::new(transforms, frame_info, channel_info, transform_info)?;
     Ok(reference_frames)
 }
+
+/// Reduces each run of consecutive samples, which are within the tolerances of the run's first
+/// sample, to the run's first and last sample.
+///
+/// Since the pose is constant within a run, step and linear interpolation yield the same result
+/// as before up to the tolerances.
+fn collapse_stationary_runs(
+    transforms: Vec<Transform>,
+    translation_eps: f64,
+    angle_eps: f64,
+) -> Vec<Transform> {
+    let is_near = |a: &Transform, b: &Transform| {
+        (a.translation - b.translation).norm() <= translation_eps
+            && a.rotation.angle_to(&b.rotation) <= angle_eps
+    };
+
+    let mut collapsed: Vec<Transform> = Vec::with_capacity(transforms.len());
+    let mut run_start_index = 0;
+    for current_transform in transforms {
+        let run_length = collapsed.len() - run_start_index;
+        if run_length > 0 && is_near(&collapsed[run_start_index], &current_transform) {
+            if run_length > 1 {
+                collapsed.pop();
+            }
+        } else {
+            run_start_index = collapsed.len();
+        }
+        collapsed.push(current_transform);
+    }
+    collapsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use nalgebra::{UnitQuaternion, Vector3};
+
+    fn document_with_rotation(rotation: &str) -> String {
+        format!(
+            r#"{{
+                "transforms": [{{
+                    "channel_id": "calibration",
+                    "frame_id": "base_link",
+                    "child_frame_id": "camera",
+                    "timestamp": {{ "sec": 0, "nanosec": 0 }},
+                    "translation": {{ "x": 1.0, "y": 2.0, "z": 3.0 }},
+                    "rotation": {rotation}
+                }}],
+                "frame_info": [],
+                "channel_info": [],
+                "transform_info": []
+            }}"#
+        )
+    }
+
+    #[test]
+    fn test_read_axis_angle_rotation() {
+        let document =
+            document_with_rotation(r#"{ "axis_angle": [0.0, 0.0, 1.5707963267948966] }"#);
+
+        let reference_frames = read_from_json_file(document.as_bytes(), None, None).unwrap();
+
+        let transform = reference_frames
+            .transforms()
+            .values()
+            .next()
+            .unwrap()
+            .first()
+            .unwrap();
+        let expected =
+            UnitQuaternion::from_axis_angle(&Vector3::z_axis(), std::f64::consts::FRAC_PI_2);
+        assert!(transform.rotation.angle_to(&expected) < 1e-12);
+    }
+
+    #[test]
+    fn test_read_ambiguous_rotation() {
+        let document = document_with_rotation(
+            r#"{ "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0, "axis_angle": [0.0, 0.0, 1.0] }"#,
+        );
+
+        let result = read_from_json_file(document.as_bytes(), None, None);
+
+        assert!(matches!(result, Err(Error::Parsing(_))));
+    }
+
+    #[test]
+    fn test_read_invalid_rotation_interpolation() {
+        let document = r#"{
+            "transforms": [],
+            "frame_info": [],
+            "channel_info": [],
+            "transform_info": [{
+                "frame_id": "base_link",
+                "child_frame_id": "camera",
+                "interpolation_method": "linear",
+                "rotation_interpolation": "slurp"
+            }]
+        }"#;
+
+        let result = read_from_json_file(document.as_bytes(), None, None);
+
+        assert!(matches!(result, Err(InvalidRotationInterpolation(value)) if value == "slurp"));
+    }
+
+    #[test]
+    fn test_read_rotation_with_annotation() {
+        let document =
+            document_with_rotation(r#"{ "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0, "frame": "imu" }"#);
+
+        let reference_frames = read_from_json_file(document.as_bytes(), None, None).unwrap();
+
+        let transform = reference_frames
+            .transforms()
+            .values()
+            .next()
+            .unwrap()
+            .first()
+            .unwrap();
+        assert_eq!(transform.rotation, UnitQuaternion::identity());
+    }
+
+    #[test]
+    fn test_read_incomplete_rotation() {
+        let document = document_with_rotation(r#"{ "x": 0.0, "y": 0.0, "w": 1.0 }"#);
+
+        let result = read_from_json_file(document.as_bytes(), None, None);
+
+        assert!(matches!(result, Err(Error::Parsing(_))));
+    }
+
+    #[test]
+    fn test_read_with_dedup_tolerance() {
+        let transform_elements: Vec<String> = [0.0, 1.0, 1.0, 1.0 + 1e-9, 1.0, 1.0, 2.0]
+            .iter()
+            .enumerate()
+            .map(|(sec, x)| {
+                format!(
+                    r#"{{
+                        "channel_id": "slam",
+                        "frame_id": "world",
+                        "child_frame_id": "base_link",
+                        "timestamp": {{ "sec": {sec}, "nanosec": 0 }},
+                        "translation": {{ "x": {x}, "y": 0.0, "z": 0.0 }},
+                        "rotation": {{ "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 }}
+                    }}"#
+                )
+            })
+            .collect();
+        let document = format!(
+            r#"{{
+                "transforms": [{}],
+                "frame_info": [],
+                "channel_info": [],
+                "transform_info": []
+            }}"#,
+            transform_elements.join(",")
+        );
+
+        let all = read_from_json_file(document.as_bytes(), None, None).unwrap();
+        let deduplicated =
+            read_from_json_file(document.as_bytes(), Some((1e-6, 1e-6)), None).unwrap();
+
+        let timestamps: Vec<i64> = deduplicated
+            .transforms()
+            .values()
+            .next()
+            .unwrap()
+            .iter()
+            .map(|t| t.timestamp.timestamp())
+            .collect();
+        assert_eq!(all.total_sample_count(), 7);
+        assert_eq!(timestamps, vec![0, 1, 5, 6]);
+    }
+
+    #[test]
+    fn test_read_self_referential_transform() {
+        let document = document_with_rotation(r#"{ "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 }"#)
+            .replace(
+                r#""child_frame_id": "camera""#,
+                r#""child_frame_id": "base_link""#,
+            );
+
+        let result = read_from_json_file(document.as_bytes(), None, None);
+
+        assert!(matches!(
+            result,
+            Err(Error::EcoordError(CyclicTransformGraph(f))) if f == FrameId::from("base_link")
+        ));
+    }
+
+    #[test]
+    fn test_read_self_referential_transform_info() {
+        let document = r#"{
+            "transforms": [],
+            "frame_info": [],
+            "channel_info": [],
+            "transform_info": [{
+                "frame_id": "base_link",
+                "child_frame_id": "base_link",
+                "interpolation_method": "linear",
+                "rotation_interpolation": "slerp"
+            }]
+        }"#;
+
+        let result = read_from_json_file(document.as_bytes(), None, None);
+
+        assert!(matches!(
+            result,
+            Err(Error::EcoordError(CyclicTransformGraph(f))) if f == FrameId::from("base_link")
+        ));
+    }
+
+    #[test]
+    fn test_read_with_time_range() {
+        let transform_element = |channel_id: &str, child_frame_id: &str, sec: i64| {
+            format!(
+                r#"{{
+                    "channel_id": "{channel_id}",
+                    "frame_id": "world",
+                    "child_frame_id": "{child_frame_id}",
+                    "timestamp": {{ "sec": {sec}, "nanosec": 0 }},
+                    "translation": {{ "x": 0.0, "y": 0.0, "z": 0.0 }},
+                    "rotation": {{ "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 }}
+                }}"#
+            )
+        };
+        let transform_elements: Vec<String> = (0..10)
+            .map(|sec| transform_element("slam", "base_link", sec))
+            .chain((0..3).map(|sec| transform_element("gnss", "antenna", sec)))
+            .chain([transform_element("calibration", "origin", 0)])
+            .collect();
+        let document = format!(
+            r#"{{
+                "transforms": [{}],
+                "frame_info": [{{ "id": "antenna", "crs_epsg": null }}],
+                "channel_info": [],
+                "transform_info": []
+            }}"#,
+            transform_elements.join(",")
+        );
+
+        let reference_frames = read_from_json_file(
+            document.as_bytes(),
+            None,
+            Some((
+                Utc.timestamp_opt(4, 0).unwrap(),
+                Utc.timestamp_opt(6, 0).unwrap(),
+            )),
+        )
+        .unwrap();
+
+        let timestamps: Vec<i64> = reference_frames.transforms()[&(
+            ChannelId::from("slam"),
+            TransformId::new(FrameId::from("world"), FrameId::from("base_link")),
+        )]
+            .iter()
+            .map(|t| t.timestamp.timestamp())
+            .collect();
+        assert_eq!(timestamps, vec![4, 5, 6]);
+        assert!(!reference_frames.contains_channel(&ChannelId::from("gnss")));
+        assert!(reference_frames.contains_channel(&ChannelId::from("calibration")));
+        assert!(reference_frames.frame_info().is_empty());
+    }
+
+    #[test]
+    fn test_read_with_invalid_time_range() {
+        let document = document_with_rotation(r#"{ "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 }"#);
+        let start = Utc.timestamp_opt(6, 0).unwrap();
+        let end = Utc.timestamp_opt(4, 0).unwrap();
+
+        let result = read_from_json_file(document.as_bytes(), None, Some((start, end)));
+
+        assert!(matches!(result, Err(InvalidTimeRange { .. })));
+    }
+
+    #[test]
+    fn test_read_all() {
+        let document = document_with_rotation(r#"{ "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 }"#);
+        let other_document = document.replace("calibration", "recalibration");
+        let documents = format!("[{document}, {other_document}]");
+
+        let all_reference_frames =
+            read_all_from_json_file(documents.as_bytes(), None, None).unwrap();
+        let single_result = read_from_json_file(documents.as_bytes(), None, None);
+
+        assert_eq!(all_reference_frames.len(), 2);
+        assert!(all_reference_frames[0].contains_channel(&ChannelId::from("calibration")));
+        assert!(all_reference_frames[1].contains_channel(&ChannelId::from("recalibration")));
+        assert!(matches!(single_result, Err(Error::Parsing(_))));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::error::Error;
+use chrono::{DateTime, Timelike, Utc};
+use ecoord_core::{ReferenceFrames, TransformId};
+use std::io::Write;
+
+const TIMELINE_CSV_HEADER: [&str; 9] = [
+    "timestamp_sec",
+    "timestamp_nanosec",
+    "translation_x",
+    "translation_y",
+    "translation_z",
+    "rotation_x",
+    "rotation_y",
+    "rotation_z",
+    "rotation_w",
+];
+const TIMELINE_CSV_DELIMITER: &str = ";";
+
+/// Writes the transform resolved at each of the timestamps as a row of a semicolon-separated CSV.
+///
+/// The transform is resolved across all channels, as in
+/// [`ReferenceFrames::derive_transform_graph`].
+pub fn export_timeline_csv<W: Write>(
+    mut writer: W,
+    reference_frames: &ReferenceFrames,
+    transform_id: &TransformId,
+    timestamps: &[DateTime<Utc>],
+) -> Result<(), Error> {
+    writeln!(
+        writer,
+        "{}",
+        TIMELINE_CSV_HEADER.join(TIMELINE_CSV_DELIMITER)
+    )?;
+
+    for current_timestamp in timestamps {
+        let isometry = reference_frames
+            .derive_transform_graph(&None, &Some(*current_timestamp))?
+            .get_isometry(transform_id)?;
+
+        let row = [
+            current_timestamp.timestamp().to_string(),
+            current_timestamp.nanosecond().to_string(),
+            isometry.translation.x.to_string(),
+            isometry.translation.y.to_string(),
+            isometry.translation.z.to_string(),
+            isometry.rotation.i.to_string(),
+            isometry.rotation.j.to_string(),
+            isometry.rotation.k.to_string(),
+            isometry.rotation.w.to_string(),
+        ];
+        writeln!(writer, "{}", row.join(TIMELINE_CSV_DELIMITER))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use ecoord_core::{ChannelId, FrameId, InterpolationMethod, Transform, TransformInfo};
+    use nalgebra::{UnitQuaternion, Vector3};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_export_timeline_csv() {
+        let transform_id = TransformId::new(FrameId::from("map"), FrameId::from("base_link"));
+        let mut transforms: HashMap<(ChannelId, TransformId), Vec<Transform>> = HashMap::new();
+        transforms.insert(
+            (ChannelId::from("slam"), transform_id.clone()),
+            vec![
+                Transform::new(
+                    Utc.timestamp_opt(0, 0).unwrap(),
+                    Vector3::new(0.0, 0.0, 0.0),
+                    UnitQuaternion::identity(),
+                ),
+                Transform::new(
+                    Utc.timestamp_opt(10, 0).unwrap(),
+                    Vector3::new(10.0, -5.0, 1.0),
+                    UnitQuaternion::from_euler_angles(0.0, 0.0, 1.0),
+                ),
+            ],
+        );
+        let mut transform_info: HashMap<TransformId, TransformInfo> = HashMap::new();
+        transform_info.insert(
+            transform_id.clone(),
+            TransformInfo::new(Some(InterpolationMethod::Linear), None),
+        );
+        let reference_frames =
+            ReferenceFrames::new(transforms, HashMap::new(), HashMap::new(), transform_info)
+                .unwrap();
+        let timestamps = vec![
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Utc.timestamp_opt(2, 500_000_000).unwrap(),
+            Utc.timestamp_opt(9, 0).unwrap(),
+        ];
+
+        let mut buffer: Vec<u8> = Vec::new();
+        export_timeline_csv(&mut buffer, &reference_frames, &transform_id, &timestamps).unwrap();
+
+        let content = String::from_utf8(buffer).unwrap();
+        let mut lines = content.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            TIMELINE_CSV_HEADER.join(TIMELINE_CSV_DELIMITER)
+        );
+        let rows: Vec<Vec<f64>> = lines
+            .map(|l| {
+                l.split(TIMELINE_CSV_DELIMITER)
+                    .map(|v| v.parse().unwrap())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(rows.len(), timestamps.len());
+        for (current_row, current_timestamp) in rows.iter().zip(&timestamps) {
+            let expected = reference_frames
+                .derive_transform_graph(&None, &Some(*current_timestamp))
+                .unwrap()
+                .get_isometry(&transform_id)
+                .unwrap();
+            assert_eq!(current_row[0], current_timestamp.timestamp() as f64);
+            assert_eq!(current_row[1], current_timestamp.nanosecond() as f64);
+            assert_eq!(
+                current_row[2..5],
+                expected.translation.vector.as_slice()[..]
+            );
+            assert_eq!(current_row[5..9], expected.rotation.coords.as_slice()[..]);
+        }
+    }
+}
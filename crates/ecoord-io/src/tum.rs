@@ -0,0 +1,182 @@
+use crate::error::Error;
+use crate::Error::InvalidTumLine;
+use chrono::{DateTime, Duration, Utc};
+use ecoord_core::{ChannelId, FrameId, ReferenceFrames, Transform, TransformId};
+use nalgebra::{Quaternion, UnitQuaternion, Vector3};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+
+/// Reads a trajectory in the format of the TUM RGB-D benchmark as the transforms of a single
+/// transform id.
+///
+/// Each line contains `timestamp tx ty tz qx qy qz qw` separated by spaces, whereby the timestamp
+/// is given in seconds since the Unix epoch. Empty lines and lines starting with `#` are skipped.
+pub fn read_tum_trajectory<R: Read>(
+    reader: R,
+    channel_id: ChannelId,
+    frame_id: FrameId,
+    child_frame_id: FrameId,
+) -> Result<ReferenceFrames, Error> {
+    let mut transforms: Vec<Transform> = Vec::new();
+    for (line_index, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let transform = parse_line(line).ok_or(InvalidTumLine(line_index + 1))?;
+        transforms.push(transform);
+    }
+
+    let transforms = HashMap::from([(
+        (channel_id, TransformId::new(frame_id, child_frame_id)),
+        transforms,
+    )]);
+    let reference_frames =
+        ReferenceFrames::new(transforms, HashMap::new(), HashMap::new(), HashMap::new())?;
+    Ok(reference_frames)
+}
+
+fn parse_line(line: &str) -> Option<Transform> {
+    let mut parts = line.split_whitespace();
+    let timestamp = parse_timestamp(parts.next()?)?;
+    let values: Vec<f64> = parts.map(|p| p.parse().ok()).collect::<Option<_>>()?;
+    let [tx, ty, tz, qx, qy, qz, qw] = values[..] else {
+        return None;
+    };
+
+    let rotation = Quaternion::new(qw, qx, qy, qz);
+    if !rotation.norm().is_normal() {
+        return None;
+    }
+    Some(Transform::new(
+        timestamp,
+        Vector3::new(tx, ty, tz),
+        UnitQuaternion::from_quaternion(rotation),
+    ))
+}
+
+/// Parses seconds with a decimal fraction without the precision loss of a `f64`.
+///
+/// The sign applies to the whole value, so that `-0.5` is half a second before the epoch.
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let (is_negative, magnitude) = match value.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, value),
+    };
+    let (sec, fraction) = magnitude.split_once('.').unwrap_or((magnitude, ""));
+    if sec.is_empty()
+        || !sec.chars().all(|c| c.is_ascii_digit())
+        || fraction.len() > 9
+        || !fraction.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let nanosec: i64 = format!("{fraction:0<9}").parse().ok()?;
+
+    let offset = Duration::try_seconds(sec.parse().ok()?)? + Duration::nanoseconds(nanosec);
+    let offset = if is_negative { -offset } else { offset };
+    DateTime::UNIX_EPOCH.checked_add_signed(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_read_tum_trajectory() {
+        let document = "# ground truth trajectory
+# timestamp tx ty tz qx qy qz qw
+1305031098.6659 1.3563 0.6305 1.6380 0.6132 0.5962 -0.3311 -0.3986
+1305031098.6758 1.3543 0.6306 1.6360 0.6129 0.5966 -0.3316 -0.3980
+
+1305031098.6858 1.3524 0.6306 1.6347 0.6123 0.5971 -0.3319 -0.3977
+";
+
+        let reference_frames = read_tum_trajectory(
+            document.as_bytes(),
+            ChannelId::from("ground_truth"),
+            FrameId::from("world"),
+            FrameId::from("camera"),
+        )
+        .unwrap();
+
+        let transforms = reference_frames.transforms().values().next().unwrap();
+        assert_eq!(transforms.len(), 3);
+        assert_eq!(
+            transforms.first().unwrap().timestamp,
+            Utc.timestamp_opt(1305031098, 665_900_000).unwrap()
+        );
+        assert_eq!(
+            transforms.last().unwrap().timestamp,
+            Utc.timestamp_opt(1305031098, 685_800_000).unwrap()
+        );
+        assert_eq!(
+            transforms.first().unwrap().translation,
+            Vector3::new(1.3563, 0.6305, 1.6380)
+        );
+    }
+
+    #[test]
+    fn test_read_tum_trajectory_invalid_line() {
+        let document = "1305031098.6659 1.3563 0.6305 1.6380\n";
+
+        let result = read_tum_trajectory(
+            document.as_bytes(),
+            ChannelId::from("ground_truth"),
+            FrameId::from("world"),
+            FrameId::from("camera"),
+        );
+
+        assert!(matches!(result, Err(InvalidTumLine(1))));
+    }
+
+    #[test]
+    fn test_read_tum_trajectory_quaternion_order() {
+        let document = "0.5 1.0 2.0 3.0 0.0 0.0 0.7071067811865476 0.7071067811865476\n";
+
+        let reference_frames = read_tum_trajectory(
+            document.as_bytes(),
+            ChannelId::from("ground_truth"),
+            FrameId::from("world"),
+            FrameId::from("camera"),
+        )
+        .unwrap();
+
+        let transform = &reference_frames.transforms().values().next().unwrap()[0];
+        assert_eq!(
+            transform.timestamp,
+            Utc.timestamp_opt(0, 500_000_000).unwrap()
+        );
+        assert!(
+            transform
+                .rotation
+                .angle_to(&UnitQuaternion::from_euler_angles(
+                    0.0,
+                    0.0,
+                    std::f64::consts::FRAC_PI_2
+                ))
+                < 1e-12
+        );
+    }
+
+    #[test]
+    fn test_parse_timestamp_sign() {
+        assert_eq!(
+            parse_timestamp("-0.5"),
+            Some(Utc.timestamp_opt(-1, 500_000_000).unwrap())
+        );
+        assert_eq!(
+            parse_timestamp("-1.25"),
+            Some(Utc.timestamp_opt(-2, 750_000_000).unwrap())
+        );
+        assert_eq!(
+            parse_timestamp("1.25"),
+            Some(Utc.timestamp_opt(1, 250_000_000).unwrap())
+        );
+        assert_eq!(parse_timestamp("--1.0"), None);
+        assert_eq!(parse_timestamp("1.-5"), None);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::error::Error;
 use crate::write_impl::write_to_json_file;
 use crate::Error::{InvalidFileExtension, NoFileExtension};
+use crate::ExtraFields;
 use crate::FILE_EXTENSION_ECOORD_FORMAT;
 use ecoord_core::ReferenceFrames;
 use std::fs::{File, OpenOptions};
@@ -13,6 +14,7 @@ use std::path::Path;
 pub struct EcoordWriter<W: Write> {
     writer: W,
     pretty_write: bool,
+    extra_fields: ExtraFields,
 }
 
 impl<W: Write> EcoordWriter<W> {
@@ -20,6 +22,7 @@ impl<W: Write> EcoordWriter<W> {
         Self {
             writer,
             pretty_write: false,
+            extra_fields: ExtraFields::default(),
         }
     }
 
@@ -28,8 +31,25 @@ impl<W: Write> EcoordWriter<W> {
         self
     }
 
+    /// Additional fields written to the document, its transforms and its frame infos, e.g. the
+    /// unknown fields returned by [`EcoordReader::finish_with_extra_fields`].
+    ///
+    /// Fields of transform samples or frames, which are not contained in the written reference
+    /// frames, are skipped.
+    ///
+    /// [`EcoordReader::finish_with_extra_fields`]: crate::EcoordReader::finish_with_extra_fields
+    pub fn with_extra_fields(mut self, extra_fields: ExtraFields) -> Self {
+        self.extra_fields = extra_fields;
+        self
+    }
+
     pub fn finish(self, reference_frames: &ReferenceFrames) -> Result<(), Error> {
-        write_to_json_file(self.writer, self.pretty_write, reference_frames)?;
+        write_to_json_file(
+            self.writer,
+            self.pretty_write,
+            reference_frames,
+            &self.extra_fields,
+        )?;
         Ok(())
     }
 }
@@ -51,3 +71,148 @@ impl EcoordWriter<File> {
         Ok(Self::new(file))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EcoordReader;
+    use serde_json::Value;
+
+    #[test]
+    fn test_extra_fields_round_trip() {
+        let document = r#"{
+            "transforms": [],
+            "frame_info": [],
+            "channel_info": [],
+            "transform_info": [],
+            "provenance": { "tool": "calibrator", "version": 3 }
+        }"#;
+        let (reference_frames, extra_fields) = EcoordReader::new(document.as_bytes())
+            .finish_with_extra_fields()
+            .unwrap();
+
+        let mut buffer: Vec<u8> = Vec::new();
+        EcoordWriter::new(&mut buffer)
+            .with_extra_fields(extra_fields)
+            .finish(&reference_frames)
+            .unwrap();
+
+        let written: Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(
+            written["provenance"],
+            serde_json::json!({ "tool": "calibrator", "version": 3 })
+        );
+    }
+
+    #[test]
+    fn test_element_extra_fields_round_trip() {
+        let document = r#"{
+            "transforms": [{
+                "channel_id": "calibration",
+                "frame_id": "base_link",
+                "child_frame_id": "camera",
+                "timestamp": { "sec": 0, "nanosec": 0 },
+                "translation": { "x": 0.1, "y": 0.0, "z": 0.5 },
+                "rotation": { "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 },
+                "covariance": [0.1, 0.2]
+            }],
+            "frame_info": [{ "id": "camera", "crs_epsg": null, "model": "acme-cam" }],
+            "channel_info": [],
+            "transform_info": []
+        }"#;
+        let (reference_frames, extra_fields) = EcoordReader::new(document.as_bytes())
+            .finish_with_extra_fields()
+            .unwrap();
+
+        let mut buffer: Vec<u8> = Vec::new();
+        EcoordWriter::new(&mut buffer)
+            .with_extra_fields(extra_fields)
+            .finish(&reference_frames)
+            .unwrap();
+
+        let written: Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(
+            written["transforms"][0]["covariance"],
+            serde_json::json!([0.1, 0.2])
+        );
+        assert_eq!(written["frame_info"][0]["model"], "acme-cam");
+        assert!(written["transforms"][0]["rotation"]
+            .get("covariance")
+            .is_none());
+    }
+
+    #[test]
+    fn test_crs_wkt_round_trip() {
+        let crs_wkt = r#"ENGCRS["site grid",EDATUM["site"],CS[Cartesian,2],UNIT["metre",1]]"#;
+        let document = format!(
+            r#"{{
+                "transforms": [{{
+                    "channel_id": "survey",
+                    "frame_id": "site",
+                    "child_frame_id": "scanner",
+                    "timestamp": {{ "sec": 0, "nanosec": 0 }},
+                    "translation": {{ "x": 0.0, "y": 0.0, "z": 0.0 }},
+                    "rotation": {{ "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 }}
+                }}],
+                "frame_info": [{{ "id": "site", "crs_epsg": null, "crs_wkt": {} }}],
+                "channel_info": [],
+                "transform_info": []
+            }}"#,
+            serde_json::to_string(crs_wkt).unwrap()
+        );
+        let reference_frames = EcoordReader::new(document.as_bytes()).finish().unwrap();
+
+        let mut buffer: Vec<u8> = Vec::new();
+        EcoordWriter::new(&mut buffer)
+            .finish(&reference_frames)
+            .unwrap();
+        let read_back = EcoordReader::new(buffer.as_slice()).finish().unwrap();
+
+        assert_eq!(
+            read_back.frame_info()[&"site".into()].crs_wkt(),
+            Some(crs_wkt)
+        );
+    }
+
+    #[test]
+    fn test_display_rotation_round_trip() {
+        let document = r#"{
+            "transforms": [{
+                "channel_id": "calibration",
+                "frame_id": "base_link",
+                "child_frame_id": "camera",
+                "timestamp": { "sec": 0, "nanosec": 0 },
+                "translation": { "x": 0.1, "y": 0.0, "z": 0.5 },
+                "rotation": { "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 }
+            }],
+            "frame_info": [
+                { "id": "base_link", "crs_epsg": null },
+                { "id": "camera", "crs_epsg": null, "display_rotation": { "x": -0.5, "y": 0.5, "z": -0.5, "w": 0.5 } }
+            ],
+            "channel_info": [],
+            "transform_info": []
+        }"#;
+        let reference_frames = EcoordReader::new(document.as_bytes()).finish().unwrap();
+
+        let mut buffer: Vec<u8> = Vec::new();
+        EcoordWriter::new(&mut buffer)
+            .finish(&reference_frames)
+            .unwrap();
+        let read_back = EcoordReader::new(buffer.as_slice()).finish().unwrap();
+
+        assert_eq!(
+            read_back.frame_info()[&"camera".into()].display_rotation(),
+            reference_frames.frame_info()[&"camera".into()].display_rotation()
+        );
+        assert!(read_back.frame_info()[&"camera".into()]
+            .display_rotation()
+            .is_some());
+        assert_eq!(
+            read_back.frame_info()[&"base_link".into()].display_rotation(),
+            None
+        );
+        assert!(!String::from_utf8(buffer)
+            .unwrap()
+            .contains(r#""id":"base_link","crs_epsg":null,"display_rotation""#));
+    }
+}
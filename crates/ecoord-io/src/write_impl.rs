@@ -1,7 +1,9 @@
 use crate::documents::{
-    ChannelInfoElement, EcoordDocument, FrameInfoElement, TransformElement, TransformInfoElement,
+    ChannelInfoElement, EcoordDocument, ExtendedEcoordDocument, FrameInfoElement, TransformElement,
+    TransformInfoElement, WithExtraFields,
 };
 use crate::error::Error;
+use crate::ExtraFields;
 use ecoord_core::ReferenceFrames;
 use std::io::Write;
 
@@ -9,31 +11,53 @@ pub fn write_to_json_file<W: Write>(
     writer: W,
     pretty_write: bool,
     reference_frames: &ReferenceFrames,
+    extra_fields: &ExtraFields,
 ) -> Result<(), Error> {
-    let mut transforms: Vec<TransformElement> = vec![];
+    let mut transforms: Vec<WithExtraFields<TransformElement>> = vec![];
     for ((current_channel_id, transform_id), current_transforms) in reference_frames.transforms() {
-        let mut current_transform_elements: Vec<TransformElement> = current_transforms
-            .iter()
-            .map(|t| TransformElement {
-                channel_id: current_channel_id.clone().into(),
-                frame_id: transform_id.frame_id.clone().into(),
-                child_frame_id: transform_id.child_frame_id.clone().into(),
-                timestamp: t.timestamp.into(),
-                duration: None,
-                translation: t.translation.into(),
-                rotation: t.rotation.into(),
-            })
-            .collect();
+        let mut current_transform_elements: Vec<WithExtraFields<TransformElement>> =
+            current_transforms
+                .iter()
+                .map(|t| WithExtraFields {
+                    element: TransformElement {
+                        channel_id: current_channel_id.clone().into(),
+                        frame_id: transform_id.frame_id.clone().into(),
+                        child_frame_id: transform_id.child_frame_id.clone().into(),
+                        timestamp: t.timestamp.into(),
+                        duration: None,
+                        translation: t.translation.into(),
+                        rotation: t.rotation.into(),
+                    },
+                    extra_fields: extra_fields
+                        .transforms
+                        .get(&(
+                            current_channel_id.clone(),
+                            transform_id.clone(),
+                            t.timestamp,
+                        ))
+                        .cloned()
+                        .unwrap_or_default(),
+                })
+                .collect();
 
         transforms.append(&mut current_transform_elements);
     }
 
-    let frame_info: Vec<FrameInfoElement> = reference_frames
+    let frame_info: Vec<WithExtraFields<FrameInfoElement>> = reference_frames
         .frame_info()
         .iter()
-        .map(|f| FrameInfoElement {
-            id: f.0.clone().into(),
-            crs_epsg: f.1.crs_epsg,
+        .map(|f| WithExtraFields {
+            element: FrameInfoElement {
+                id: f.0.clone().into(),
+                crs_epsg: f.1.crs_epsg,
+                crs_wkt: f.1.crs_wkt.clone(),
+                display_rotation: f.1.display_rotation.map(|r| r.into()),
+            },
+            extra_fields: extra_fields
+                .frame_info
+                .get(f.0)
+                .cloned()
+                .unwrap_or_default(),
         })
         .collect();
 
@@ -53,14 +77,18 @@ pub fn write_to_json_file<W: Write>(
             frame_id: f.0.frame_id.clone().into(),
             child_frame_id: f.0.child_frame_id.clone().into(),
             interpolation_method: f.1.interpolation_method.map(|i| i.as_str().into()),
+            rotation_interpolation: f.1.rotation_interpolation.map(|i| i.as_str().into()),
         })
         .collect();
 
-    let frames_document = EcoordDocument {
-        transforms,
-        frame_info,
-        channel_info,
-        transform_info,
+    let frames_document: ExtendedEcoordDocument = WithExtraFields {
+        element: EcoordDocument {
+            transforms,
+            frame_info,
+            channel_info,
+            transform_info,
+        },
+        extra_fields: extra_fields.document.clone(),
     };
 
     if pretty_write {
@@ -0,0 +1,103 @@
+use ecoord_core::Transform;
+use nalgebra::{Isometry3, Matrix4, Quaternion, UnitQuaternion, Vector3};
+
+/// Blends a list of weighted transforms to a single isometry.
+///
+/// The weights are normalized, the translation is computed as the weighted sum and the rotation
+/// as the weighted quaternion average (eigenvector of the largest eigenvalue of the weighted
+/// outer product sum, which is insensitive to the quaternion sign).
+/// Returns `None`, if the list is empty or the weights sum up to zero.
+pub fn weighted_blend(transforms: &[(Transform, f64)]) -> Option<Isometry3<f64>> {
+    let weight_sum: f64 = transforms.iter().map(|(_, w)| w).sum();
+    if weight_sum.abs() < f64::EPSILON {
+        return None;
+    }
+
+    let translation: Vector3<f64> = transforms
+        .iter()
+        .map(|(t, w)| t.translation * (w / weight_sum))
+        .sum();
+
+    let accumulated: Matrix4<f64> = transforms
+        .iter()
+        .map(|(t, w)| {
+            let q = t.rotation.coords;
+            q * q.transpose() * (w / weight_sum)
+        })
+        .sum();
+    let eigen = accumulated.symmetric_eigen();
+    let (max_index, _) = eigen.eigenvalues.argmax();
+    let rotation = UnitQuaternion::from_quaternion(Quaternion::from(
+        eigen.eigenvectors.column(max_index).into_owned(),
+    ));
+
+    Some(Isometry3::from_parts(translation.into(), rotation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_weighted_blend_equal_weights() {
+        let timestamp = Utc.timestamp_opt(1, 0).unwrap();
+        let transforms = vec![
+            (
+                Transform::new(
+                    timestamp,
+                    Vector3::new(0.0, 0.0, 0.0),
+                    UnitQuaternion::from_euler_angles(0.0, 0.0, 0.1),
+                ),
+                2.0,
+            ),
+            (
+                Transform::new(
+                    timestamp,
+                    Vector3::new(3.0, 6.0, 0.0),
+                    UnitQuaternion::from_euler_angles(0.0, 0.0, 0.2),
+                ),
+                2.0,
+            ),
+            (
+                Transform::new(
+                    timestamp,
+                    Vector3::new(6.0, 0.0, 3.0),
+                    // negated quaternion represents the same rotation
+                    UnitQuaternion::new_unchecked(
+                        -UnitQuaternion::from_euler_angles(0.0, 0.0, 0.3).into_inner(),
+                    ),
+                ),
+                2.0,
+            ),
+        ];
+
+        let result = weighted_blend(&transforms).unwrap();
+
+        assert_relative_eq!(
+            result.translation.vector,
+            Vector3::new(3.0, 2.0, 1.0),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            result
+                .rotation
+                .angle_to(&UnitQuaternion::from_euler_angles(0.0, 0.0, 0.2)),
+            0.0,
+            epsilon = 1e-7
+        );
+    }
+
+    #[test]
+    fn test_weighted_blend_zero_weights() {
+        let transform = Transform::new(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::new(1.0, 0.0, 0.0),
+            UnitQuaternion::identity(),
+        );
+
+        assert!(weighted_blend(&[]).is_none());
+        assert!(weighted_blend(&[(transform.clone(), 1.0), (transform, -1.0)]).is_none());
+    }
+}
@@ -0,0 +1,66 @@
+use ecoord_core::Transform;
+use nalgebra::Isometry3;
+
+/// Composes a sequence of transforms to a single isometry.
+///
+/// The transforms are multiplied from left to right, so that the first transform is the
+/// outermost one. An empty slice results in the identity.
+pub fn chain(transforms: &[Transform]) -> Isometry3<f64> {
+    transforms
+        .iter()
+        .fold(Isometry3::identity(), |acc, t| acc * t.isometry())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use chrono::{TimeZone, Utc};
+    use nalgebra::{UnitQuaternion, Vector3};
+
+    #[test]
+    fn test_chain_empty() {
+        let result = chain(&[]);
+
+        assert_eq!(result, Isometry3::identity());
+    }
+
+    #[test]
+    fn test_chain_single() {
+        let transform = Transform::new(
+            Utc.timestamp_opt(1, 0).unwrap(),
+            Vector3::new(1.0, 2.0, 3.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, std::f64::consts::FRAC_PI_2),
+        );
+
+        let expected = transform.isometry();
+        let result = chain(&[transform]);
+
+        assert_relative_eq!(result, expected, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_chain_multiple() {
+        let timestamp = Utc.timestamp_opt(1, 0).unwrap();
+        let transform_a = Transform::new(
+            timestamp,
+            Vector3::new(10.0, 0.0, 0.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, std::f64::consts::FRAC_PI_2),
+        );
+        let transform_b = Transform::new(
+            timestamp,
+            Vector3::new(0.0, 5.0, 0.0),
+            UnitQuaternion::from_euler_angles(std::f64::consts::FRAC_PI_4, 0.0, 0.0),
+        );
+        let transform_c = Transform::new(
+            timestamp,
+            Vector3::new(1.0, 1.0, 1.0),
+            UnitQuaternion::identity(),
+        );
+        let expected = transform_a.isometry() * transform_b.isometry() * transform_c.isometry();
+
+        let result = chain(&[transform_a, transform_b, transform_c]);
+
+        assert_relative_eq!(result, expected, epsilon = f64::EPSILON);
+    }
+}
@@ -4,4 +4,7 @@ use thiserror::Error;
 pub enum Error {
     #[error(transparent)]
     EcoordError(#[from] ecoord_core::Error),
+
+    #[error("a path requires at least two transforms, but {0} were requested")]
+    InvalidPathLength(usize),
 }
@@ -1,5 +1,16 @@
+mod blend;
+mod compose;
 mod error;
-mod filter;
+mod path;
 
 #[doc(inline)]
 pub use crate::error::Error;
+
+#[doc(inline)]
+pub use crate::compose::chain;
+
+#[doc(inline)]
+pub use crate::blend::weighted_blend;
+
+#[doc(inline)]
+pub use crate::path::slerp_path;
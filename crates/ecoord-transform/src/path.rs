@@ -0,0 +1,106 @@
+use crate::error::Error;
+use crate::Error::InvalidPathLength;
+use chrono::Duration;
+use ecoord_core::Error::DurationOverflow;
+use ecoord_core::{RotationInterpolation, Transform};
+
+/// Returns `n` evenly spaced transforms from `from` to `to`, including both.
+///
+/// Timestamps and translations are interpolated linearly and rotations by slerp along the
+/// shorter arc. Fails, if `n` is less than two or the time span is not representable in
+/// nanoseconds.
+pub fn slerp_path(from: &Transform, to: &Transform, n: usize) -> Result<Vec<Transform>, Error> {
+    if n < 2 {
+        return Err(InvalidPathLength(n));
+    }
+    let time_span = to.timestamp - from.timestamp;
+    let time_span_nanoseconds = time_span
+        .num_nanoseconds()
+        .ok_or(DurationOverflow(time_span))? as i128;
+
+    let path = (0..n)
+        .map(|i| {
+            let weight = i as f64 / (n - 1) as f64;
+            let offset = time_span_nanoseconds * i as i128 / (n - 1) as i128;
+            Transform::new(
+                from.timestamp + Duration::nanoseconds(offset as i64),
+                from.translation * (1.0 - weight) + to.translation * weight,
+                RotationInterpolation::Slerp.interpolate(&from.rotation, &to.rotation, weight),
+            )
+        })
+        .collect();
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeZone, Utc};
+    use nalgebra::{UnitQuaternion, Vector3};
+
+    fn path_endpoints() -> (Transform, Transform) {
+        let from = Transform::new(
+            Utc.timestamp_opt(10, 0).unwrap(),
+            Vector3::new(0.0, 0.0, 0.0),
+            UnitQuaternion::identity(),
+        );
+        let to = Transform::new(
+            Utc.timestamp_opt(12, 0).unwrap(),
+            Vector3::new(2.0, -4.0, 6.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, std::f64::consts::FRAC_PI_2),
+        );
+        (from, to)
+    }
+
+    #[test]
+    fn test_slerp_path_endpoints() {
+        let (from, to) = path_endpoints();
+
+        let path = slerp_path(&from, &to, 2).unwrap();
+
+        assert_eq!(path, vec![from, to]);
+    }
+
+    #[test]
+    fn test_slerp_path_midpoint() {
+        let (from, to) = path_endpoints();
+
+        let path = slerp_path(&from, &to, 3).unwrap();
+
+        assert_eq!(path.len(), 3);
+        assert_eq!(path[0], from);
+        assert_eq!(path[1].timestamp, Utc.timestamp_opt(11, 0).unwrap());
+        assert!((path[1].translation - Vector3::new(1.0, -2.0, 3.0)).norm() < 1e-12);
+        assert!((path[1].rotation.angle() - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+        assert!((path[2].translation - to.translation).norm() < 1e-12);
+        assert!(path[2].rotation.angle_to(&to.rotation) < 1e-12);
+    }
+
+    #[test]
+    fn test_slerp_path_unit_rotations() {
+        let (from, mut to) = path_endpoints();
+        to.rotation = UnitQuaternion::from_euler_angles(2.0, -1.0, 3.0);
+
+        let path = slerp_path(&from, &to, 17).unwrap();
+
+        assert!(path
+            .iter()
+            .all(|t| (t.rotation.quaternion().norm() - 1.0).abs() < 1e-12));
+        assert!(path.windows(2).all(|t| t[0].timestamp < t[1].timestamp));
+    }
+
+    #[test]
+    fn test_slerp_path_invalid() {
+        let (from, mut to) = path_endpoints();
+
+        let too_short = slerp_path(&from, &to, 1);
+        to.timestamp = DateTime::<Utc>::MAX_UTC;
+        let overflow = slerp_path(&from, &to, 3);
+
+        assert!(matches!(too_short, Err(InvalidPathLength(1))));
+        assert!(matches!(
+            overflow,
+            Err(Error::EcoordError(DurationOverflow(_)))
+        ));
+    }
+}
@@ -47,6 +47,8 @@
 //!             - `y`: [f64]
 //!             - `z`: [f64]
 //!             - `w`: [f64]
+//!         - alternatively, `rotation` as axis-angle (not combinable with the quaternion fields)
+//!             - `axis_angle`: \[[f64]; 3\] rotation vector with the angle in radians as magnitude
 //!     - `channel_info`: additional information on channels
 //!         - `ìd`: [String]
 //!         - `priority`: [Option]<[i32]>
@@ -56,17 +58,26 @@
 //!         - `id`: [String]
 //!             - unique identifier
 //!         - `crs_epsg`: [Option]<[i32]>
+//!         - `crs_wkt`: [Option]<[String]>, exclusive to `crs_epsg`
+//!         - `display_rotation`: [Option] Quaternion for visualization only, e.g. for Z-forward camera frames
+//!             - `x`, `y`, `z`, `w`: [f64]
 //!     - `transform_info`
 //!         - `frame_id`: [String]
 //!         - `child_frame_id`: [String]
 //!         - `interpolation_method`: [Option]<[String]>
 //!             - `step` (default): piecewise constant interpolation
 //!             - `linear`: linear interpolation
+//!             - `cubic_hermite`: cubic Hermite interpolation (linear in the first and last interval)
+//!         - `rotation_interpolation`: [Option]<[String]>
+//!             - `slerp` (default): spherical linear interpolation
+//!             - `nlerp`: normalized linear interpolation (faster, less accurate for large angles)
 //!
 
 pub use ecoord_core::{
-    merge, ChannelId, ChannelInfo, Error, FrameId, FrameInfo, InterpolationMethod, ReferenceFrames,
+    merge, merge_with_policy, AffineTransform, ChannelId, ChannelInfo, CrsAxisOrder, Error,
+    FrameId, FrameInfo, InterpolationMethod, MergePolicy, ReferenceFrames, RotationInterpolation,
     SphericalPoint3, Transform, TransformId, TransformInfo, UnitSphericalPoint3,
+    GIMBAL_LOCK_EPSILON,
 };
 
 pub use ecoord_io as io;